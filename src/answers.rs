@@ -0,0 +1,195 @@
+use std::{collections::BTreeMap, fs, path::Path};
+
+use crate::{
+    error::{Error, Result},
+    input::Source,
+    solution::Answer,
+};
+
+/// Known answers of a single input.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Expected {
+    pub part_one: Option<Answer>,
+    pub part_two: Option<Answer>,
+}
+
+/// Outcome of comparing one part against its known answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Check {
+    Pass,
+    Fail { expected: Answer, actual: Answer },
+    Missing,
+}
+
+impl Check {
+    pub fn new(expected: Option<&Answer>, actual: &Answer) -> Self {
+        match expected {
+            Some(expected) if expected == actual => Check::Pass,
+            Some(expected) => Check::Fail {
+                expected: expected.clone(),
+                actual: actual.clone(),
+            },
+            None => Check::Missing,
+        }
+    }
+}
+
+/// The answers file, a small subset of TOML with one table per input:
+///
+/// ```toml
+/// [day_1]
+/// part_one = 71924
+/// part_two = "text, or a multi-line \"\"\" string for images"
+///
+/// [day_1.example]
+/// part_one = 24000
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Answers {
+    sections: BTreeMap<String, Expected>,
+}
+
+impl Answers {
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path).map_err(|source| Error::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+
+        Self::parse(&content).map_err(|(line, reason)| Error::Config {
+            path: path.to_path_buf(),
+            line,
+            reason,
+        })
+    }
+
+    /// Errors are returned as the 1-based line and the reason.
+    pub fn parse(content: &str) -> std::result::Result<Self, (usize, String)> {
+        let mut sections = BTreeMap::new();
+        let mut current: Option<String> = None;
+
+        let mut lines = content.lines().enumerate();
+        while let Some((index, line)) = lines.next() {
+            let line = line.trim();
+
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                let name = name.trim().to_string();
+                sections
+                    .entry(name.clone())
+                    .or_insert_with(Expected::default);
+                current = Some(name);
+                continue;
+            }
+
+            let (key, value) = line
+                .split_once('=')
+                .ok_or((index + 1, "expected `key = value`".to_string()))?;
+            let (key, value) = (key.trim(), value.trim());
+
+            let section = current
+                .as_ref()
+                .and_then(|name| sections.get_mut(name))
+                .ok_or((index + 1, "value outside of a section".to_string()))?;
+
+            let answer = if let Some(rest) = value.strip_prefix("\"\"\"") {
+                let mut text = Vec::new();
+                if !rest.is_empty() {
+                    text.push(rest.to_string());
+                }
+
+                loop {
+                    let (_, line) = lines
+                        .next()
+                        .ok_or((index + 1, "unterminated multi-line string".to_string()))?;
+
+                    if let Some(last) = line.strip_suffix("\"\"\"") {
+                        if !last.is_empty() {
+                            text.push(last.to_string());
+                        }
+                        break;
+                    }
+
+                    text.push(line.to_string());
+                }
+
+                Answer::Image(text)
+            } else if let Some(text) = value.strip_prefix('"').and_then(|v| v.strip_suffix('"')) {
+                Answer::Text(text.replace("\\\"", "\"").replace("\\\\", "\\"))
+            } else {
+                value
+                    .parse::<i64>()
+                    .map(Answer::Number)
+                    .map_err(|_| (index + 1, format!("invalid value `{}`", value)))?
+            };
+
+            match key {
+                "part_one" => section.part_one = Some(answer),
+                "part_two" => section.part_two = Some(answer),
+                _ => return Err((index + 1, format!("unknown key `{}`", key))),
+            }
+        }
+
+        Ok(Self { sections })
+    }
+
+    /// Known answers of one of the inputs of a day.
+    pub fn get(&self, day: u8, source: Source) -> Option<&Expected> {
+        self.sections.get(&source.section(day))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_numbers_strings_and_images() {
+        let answers = Answers::parse(
+            "# comment
+[day_5]
+part_one = \"CMZ\"
+
+[day_5.example.2]
+part_one = 12
+part_two = \"\"\"
+#.#
+.#.
+\"\"\"
+",
+        )
+        .unwrap();
+
+        let real = answers.get(5, Source::Real).unwrap();
+        assert_eq!(real.part_one, Some(Answer::Text("CMZ".to_string())));
+        assert_eq!(real.part_two, None);
+
+        let example = answers.get(5, Source::Example(2)).unwrap();
+        assert_eq!(example.part_one, Some(Answer::Number(12)));
+        assert_eq!(
+            example.part_two,
+            Some(Answer::Image(vec!["#.#".to_string(), ".#.".to_string()]))
+        );
+    }
+
+    #[test]
+    fn reports_the_line_of_errors() {
+        assert_eq!(Answers::parse("part_one = 1").unwrap_err().0, 1);
+        assert_eq!(Answers::parse("[day_1]\n\nanswer = 1").unwrap_err().0, 3);
+        assert_eq!(Answers::parse("[day_1]\npart_one = x").unwrap_err().0, 2);
+    }
+
+    #[test]
+    fn checks_answers() {
+        let answer = Answer::Number(1);
+        assert_eq!(Check::new(Some(&answer), &answer), Check::Pass);
+        assert_eq!(Check::new(None, &answer), Check::Missing);
+        assert!(matches!(
+            Check::new(Some(&Answer::Number(2)), &answer),
+            Check::Fail { .. }
+        ));
+    }
+}
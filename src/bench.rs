@@ -0,0 +1,91 @@
+use std::time::Duration;
+
+use advent_of_code_22::{error::Result, find_day, solution::Timings, Runner};
+
+#[derive(Debug, Clone, Copy)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    fn from_samples(mut samples: Vec<Duration>) -> Self {
+        samples.sort();
+
+        let middle = samples.len() / 2;
+        let median = if samples.len() % 2 == 0 {
+            (samples[middle - 1] + samples[middle]) / 2
+        } else {
+            samples[middle]
+        };
+
+        Self {
+            min: samples[0],
+            median,
+            max: samples[samples.len() - 1],
+        }
+    }
+}
+
+/// Statistics of every step of a day over all the runs.
+pub struct DayStats {
+    pub parse: Stats,
+    pub part_one: Stats,
+    pub part_two: Stats,
+    pub total: Stats,
+}
+
+/// Runs a day `runs` times, failing on the first error.
+pub fn bench(runner: Runner, input: &str, runs: usize) -> Result<DayStats> {
+    let mut timings = Vec::with_capacity(runs);
+
+    for _ in 0..runs {
+        timings.push(runner(input)?.timings);
+    }
+
+    let stats =
+        |step: fn(&Timings) -> Duration| Stats::from_samples(timings.iter().map(step).collect());
+
+    Ok(DayStats {
+        parse: stats(|t| t.parse),
+        part_one: stats(|t| t.part_one),
+        part_two: stats(|t| t.part_two),
+        total: stats(Timings::total),
+    })
+}
+
+fn print_row(day: &str, step: &str, stats: &Stats) {
+    println!(
+        "{:>4}  {:<8}  {:>12.2?}  {:>12.2?}  {:>12.2?}",
+        day, step, stats.min, stats.median, stats.max
+    );
+}
+
+pub fn print_table(results: &[(u8, DayStats)], runs: usize) {
+    println!("Benchmark over {} runs:", runs);
+    println!(
+        "{:>4}  {:<8}  {:>12}  {:>12}  {:>12}",
+        "Day", "Step", "Min", "Median", "Max"
+    );
+
+    for (day, stats) in results {
+        let day = day.to_string();
+        print_row(&day, "parse", &stats.parse);
+        print_row("", "part 1", &stats.part_one);
+        print_row("", "part 2", &stats.part_two);
+        print_row("", "total", &stats.total);
+    }
+
+    for (day, _) in results {
+        if find_day(*day).is_some_and(|day| day.solves_while_parsing) {
+            println!("Day {} solves both parts while parsing.", day);
+        }
+    }
+
+    let median_sum = results
+        .iter()
+        .map(|(_, s)| s.total.median)
+        .sum::<Duration>();
+    println!("Sum of the median totals: {:.2?}", median_sum);
+}
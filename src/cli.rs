@@ -0,0 +1,607 @@
+use std::{fmt, ops::RangeInclusive, path::PathBuf, time::Duration};
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Command {
+    Run {
+        days: Vec<u8>,
+        time: bool,
+        example: bool,
+        json: bool,
+        parallel: bool,
+        input: Option<PathBuf>,
+    },
+    Bench {
+        days: Vec<u8>,
+        runs: usize,
+        input: Option<PathBuf>,
+    },
+    Verify {
+        days: Vec<u8>,
+        example: bool,
+    },
+    Watch {
+        days: Vec<u8>,
+        example: bool,
+        interval: Duration,
+    },
+    Check {
+        days: Vec<u8>,
+        example: bool,
+    },
+    Fetch {
+        days: Vec<u8>,
+        url: Option<String>,
+        session: Option<String>,
+    },
+    Elves {
+        example: bool,
+        input: Option<PathBuf>,
+        top: usize,
+        lenient: bool,
+    },
+    Guide {
+        example: bool,
+        input: Option<PathBuf>,
+        rules: Option<PathBuf>,
+        letters: Letters,
+    },
+    NewDay(u8),
+    List,
+    Help,
+}
+
+/// Mappings like `X=rock, Y=paper` replacing what the letters of the day 2 guide stand for.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Letters {
+    pub opponents: Option<String>,
+    pub responses: Option<String>,
+    pub states: Option<String>,
+}
+
+const DEFAULT_RUNS: usize = 10;
+const DEFAULT_INTERVAL_MS: u64 = 500;
+const DEFAULT_TOP: usize = 3;
+/// Every day of the calendar, implemented or not.
+const CALENDAR: RangeInclusive<u8> = 1..=25;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum CliError {
+    MissingCommand,
+    UnknownCommand(String),
+    MissingDays,
+    InvalidDay(String),
+    UnknownDay(u8),
+    UnknownFlag(String),
+    MissingValue(String),
+    InvalidValue(String, String),
+    InputNeedsOneDay,
+    DayExists(u8),
+    UnexpectedArgument(String),
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CliError::MissingCommand => write!(f, "no command given"),
+            CliError::UnknownCommand(cmd) => write!(f, "unknown command: {}", cmd),
+            CliError::MissingDays => write!(f, "no days given, use a day, a range or --all"),
+            CliError::InvalidDay(s) => write!(f, "invalid day or range: {}", s),
+            CliError::UnknownDay(day) => write!(f, "day {} is not implemented", day),
+            CliError::UnknownFlag(flag) => write!(f, "unknown flag: {}", flag),
+            CliError::MissingValue(flag) => write!(f, "missing value for {}", flag),
+            CliError::InvalidValue(flag, value) => {
+                write!(f, "invalid value for {}: {}", flag, value)
+            }
+            CliError::InputNeedsOneDay => write!(f, "--input can only be used with a single day"),
+            CliError::DayExists(day) => write!(f, "day {} is already implemented", day),
+            CliError::UnexpectedArgument(arg) => write!(f, "unexpected argument: {}", arg),
+        }
+    }
+}
+
+pub const USAGE: &str = "Usage:
+  aoc run <DAY>...      Run the given days, e.g. `7`, `1..=12` or `3..6`
+  aoc run --all         Run every implemented day
+      --time            Show how long parsing and each part took
+      --example         Use the examples of each day instead of the real input
+      --json            Print the answers and timings as JSON
+      --parallel        Run the days, and the parts of each day, on separate threads
+      --input <PATH>    Read the input from a file, or `-` for stdin
+  aoc bench <DAY>...    Time the given days over several runs
+      --runs <N>        Amount of runs per day (default: 10)
+      --input <PATH>    Read the input from a file, or `-` for stdin
+  aoc verify <DAY>...   Check the answers against data/answers.toml
+      --example         Check the examples of each day instead of the real input
+  aoc watch <DAY>...    Re-run the given days whenever their input or source changes
+      --example         Watch the examples of each day instead of the real input
+      --interval <MS>   How often to look for changes (default: 500)
+  aoc check <DAY>...    Look for missing, empty or badly copied inputs
+      --example         Check the examples of each day instead of the real input
+  aoc fetch <DAY>...    Download the inputs of any day of the calendar into data/
+      --url <URL>       Base URL of the puzzles (default: $AOC_URL)
+      --session <TOKEN> Session cookie of your account (default: $AOC_SESSION)
+  aoc elves             Rank the day 1 elves and describe what they carry
+      --example         Use the examples instead of the real input
+      --input <PATH>    Read the inventory from a file, or `-` for stdin
+      --top <N>         Amount of elves to rank (default: 3)
+      --lenient         Skip the lines that do not hold calories and list them
+  aoc guide             Score the day 2 guide under every way of lettering the shapes to play
+      --example         Use the examples instead of the real input
+      --input <PATH>    Read the guide from a file, or `-` for stdin
+      --rules <PATH>    Play by a rule file like data/day_2.rpsls.rules
+      --opponents <MAP> Letters of the first column, e.g. `A=rock, B=paper, C=scissors`
+      --responses <MAP> Letters of the second column read as shapes, e.g. `X=rock, Y=paper`
+      --states <MAP>    Letters of the second column read as outcomes, e.g. `X=lose, Y=draw`
+  aoc new-day <DAY>     Create the module, input and example of a day, and register it
+  aoc list              List the implemented days
+  aoc help              Show this message";
+
+fn parse_day(s: &str) -> Result<u8, CliError> {
+    s.trim()
+        .parse::<u8>()
+        .map_err(|_| CliError::InvalidDay(s.to_string()))
+}
+
+fn parse_selector(s: &str) -> Result<RangeInclusive<u8>, CliError> {
+    if let Some((from, to)) = s.split_once("..=") {
+        Ok(parse_day(from)?..=parse_day(to)?)
+    } else if let Some((from, to)) = s.split_once("..") {
+        let to = parse_day(to)?
+            .checked_sub(1)
+            .ok_or_else(|| CliError::InvalidDay(s.to_string()))?;
+        Ok(parse_day(from)?..=to)
+    } else {
+        let day = parse_day(s)?;
+        Ok(day..=day)
+    }
+}
+
+/// Arguments of a command, split into day selectors and `--flag [value]` pairs.
+struct Args<'a> {
+    positional: Vec<&'a String>,
+    flags: Vec<(&'static str, Option<&'a String>)>,
+}
+
+impl<'a> Args<'a> {
+    /// `flags` lists the accepted flags, along with whether they expect a value.
+    fn split(args: &'a [String], flags: &[(&'static str, bool)]) -> Result<Self, CliError> {
+        let mut positional = Vec::new();
+        let mut found = Vec::new();
+
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            if !arg.starts_with("--") {
+                positional.push(arg);
+                continue;
+            }
+
+            let (flag, takes_value) = flags
+                .iter()
+                .find(|(flag, _)| flag == arg)
+                .ok_or_else(|| CliError::UnknownFlag(arg.to_string()))?;
+
+            let value = if *takes_value {
+                Some(
+                    args.next()
+                        .ok_or_else(|| CliError::MissingValue(arg.to_string()))?,
+                )
+            } else {
+                None
+            };
+
+            found.push((*flag, value));
+        }
+
+        Ok(Self {
+            positional,
+            flags: found,
+        })
+    }
+
+    fn has(&self, flag: &str) -> bool {
+        self.flags.iter().any(|(f, _)| *f == flag)
+    }
+
+    fn value(&self, flag: &str) -> Option<&'a String> {
+        self.flags
+            .iter()
+            .rev()
+            .find(|(f, _)| *f == flag)
+            .and_then(|(_, value)| *value)
+    }
+
+    fn parsed<T: std::str::FromStr>(&self, flag: &str) -> Result<Option<T>, CliError> {
+        self.value(flag)
+            .map(|value| {
+                value
+                    .parse()
+                    .map_err(|_| CliError::InvalidValue(flag.to_string(), value.to_string()))
+            })
+            .transpose()
+    }
+}
+
+/// The `--input` flag, which only makes sense for a single day.
+fn parse_input(args: &Args, days: &[u8]) -> Result<Option<PathBuf>, CliError> {
+    match args.value("--input") {
+        Some(_) if days.len() != 1 => Err(CliError::InputNeedsOneDay),
+        input => Ok(input.map(PathBuf::from)),
+    }
+}
+
+fn parse_days(args: &Args, available: &[u8]) -> Result<Vec<u8>, CliError> {
+    if args.has("--all") {
+        return Ok(available.to_vec());
+    }
+
+    let args = &args.positional;
+
+    let mut days = Vec::new();
+
+    for arg in args {
+        let range = parse_selector(arg)?;
+
+        if range.is_empty() {
+            return Err(CliError::InvalidDay(arg.to_string()));
+        }
+
+        for day in range {
+            if !available.contains(&day) {
+                return Err(CliError::UnknownDay(day));
+            }
+
+            if !days.contains(&day) {
+                days.push(day);
+            }
+        }
+    }
+
+    if days.is_empty() {
+        return Err(CliError::MissingDays);
+    }
+
+    Ok(days)
+}
+
+/// Parses the arguments given after the binary name.
+pub fn parse(args: &[String], available: &[u8]) -> Result<Command, CliError> {
+    match args.split_first() {
+        Some((cmd, rest)) => match cmd.as_str() {
+            "run" => {
+                let args = Args::split(
+                    rest,
+                    &[
+                        ("--all", false),
+                        ("--time", false),
+                        ("--example", false),
+                        ("--json", false),
+                        ("--parallel", false),
+                        ("--input", true),
+                    ],
+                )?;
+                let days = parse_days(&args, available)?;
+
+                Ok(Command::Run {
+                    input: parse_input(&args, &days)?,
+                    days,
+                    time: args.has("--time"),
+                    example: args.has("--example"),
+                    json: args.has("--json"),
+                    parallel: args.has("--parallel"),
+                })
+            }
+            "bench" => {
+                let args = Args::split(
+                    rest,
+                    &[("--all", false), ("--runs", true), ("--input", true)],
+                )?;
+                let runs = args.parsed("--runs")?.unwrap_or(DEFAULT_RUNS);
+
+                if runs == 0 {
+                    return Err(CliError::InvalidValue(
+                        "--runs".to_string(),
+                        "0".to_string(),
+                    ));
+                }
+
+                let days = parse_days(&args, available)?;
+
+                Ok(Command::Bench {
+                    input: parse_input(&args, &days)?,
+                    days,
+                    runs,
+                })
+            }
+            "verify" => {
+                let args = Args::split(rest, &[("--all", false), ("--example", false)])?;
+                Ok(Command::Verify {
+                    days: parse_days(&args, available)?,
+                    example: args.has("--example"),
+                })
+            }
+            "watch" => {
+                let args = Args::split(
+                    rest,
+                    &[("--all", false), ("--example", false), ("--interval", true)],
+                )?;
+                let interval = args.parsed("--interval")?.unwrap_or(DEFAULT_INTERVAL_MS);
+
+                if interval == 0 {
+                    return Err(CliError::InvalidValue(
+                        "--interval".to_string(),
+                        "0".to_string(),
+                    ));
+                }
+
+                Ok(Command::Watch {
+                    days: parse_days(&args, available)?,
+                    example: args.has("--example"),
+                    interval: Duration::from_millis(interval),
+                })
+            }
+            "check" => {
+                let args = Args::split(rest, &[("--all", false), ("--example", false)])?;
+                Ok(Command::Check {
+                    days: parse_days(&args, available)?,
+                    example: args.has("--example"),
+                })
+            }
+            "fetch" => {
+                let args = Args::split(
+                    rest,
+                    &[("--all", false), ("--url", true), ("--session", true)],
+                )?;
+                Ok(Command::Fetch {
+                    days: parse_days(&args, &CALENDAR.collect::<Vec<_>>())?,
+                    url: args.value("--url").cloned(),
+                    session: args.value("--session").cloned(),
+                })
+            }
+            "elves" => {
+                let args = Args::split(
+                    rest,
+                    &[
+                        ("--example", false),
+                        ("--input", true),
+                        ("--top", true),
+                        ("--lenient", false),
+                    ],
+                )?;
+
+                if let Some(arg) = args.positional.first() {
+                    return Err(CliError::UnexpectedArgument(arg.to_string()));
+                }
+
+                let top = args.parsed("--top")?.unwrap_or(DEFAULT_TOP);
+                if top == 0 {
+                    return Err(CliError::InvalidValue("--top".to_string(), "0".to_string()));
+                }
+
+                Ok(Command::Elves {
+                    example: args.has("--example"),
+                    input: args.value("--input").map(PathBuf::from),
+                    top,
+                    lenient: args.has("--lenient"),
+                })
+            }
+            "guide" => {
+                let args = Args::split(
+                    rest,
+                    &[
+                        ("--example", false),
+                        ("--input", true),
+                        ("--rules", true),
+                        ("--opponents", true),
+                        ("--responses", true),
+                        ("--states", true),
+                    ],
+                )?;
+
+                if let Some(arg) = args.positional.first() {
+                    return Err(CliError::UnexpectedArgument(arg.to_string()));
+                }
+
+                Ok(Command::Guide {
+                    example: args.has("--example"),
+                    input: args.value("--input").map(PathBuf::from),
+                    rules: args.value("--rules").map(PathBuf::from),
+                    letters: Letters {
+                        opponents: args.value("--opponents").cloned(),
+                        responses: args.value("--responses").cloned(),
+                        states: args.value("--states").cloned(),
+                    },
+                })
+            }
+            "new-day" => {
+                let args = Args::split(rest, &[])?;
+                let day = match args.positional.as_slice() {
+                    [day] => parse_day(day)?,
+                    [] => return Err(CliError::MissingDays),
+                    [_, extra, ..] => return Err(CliError::InvalidDay(extra.to_string())),
+                };
+
+                if !CALENDAR.contains(&day) {
+                    Err(CliError::InvalidDay(day.to_string()))
+                } else if available.contains(&day) {
+                    Err(CliError::DayExists(day))
+                } else {
+                    Ok(Command::NewDay(day))
+                }
+            }
+            "list" => Ok(Command::List),
+            "help" | "--help" | "-h" => Ok(Command::Help),
+            _ => Err(CliError::UnknownCommand(cmd.to_string())),
+        },
+        None => Err(CliError::MissingCommand),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const AVAILABLE: [u8; 12] = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12];
+
+    fn parse_args(args: &str) -> Result<Command, CliError> {
+        let args = args
+            .split_whitespace()
+            .map(String::from)
+            .collect::<Vec<_>>();
+        parse(&args, &AVAILABLE)
+    }
+
+    fn run_days(args: &str) -> Vec<u8> {
+        match parse_args(args) {
+            Ok(Command::Run { days, .. }) => days,
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn selects_days_and_ranges() {
+        assert_eq!(run_days("run 7"), vec![7]);
+        assert_eq!(run_days("run 1..=3"), vec![1, 2, 3]);
+        assert_eq!(run_days("run 3..5 4 9"), vec![3, 4, 9]);
+        assert_eq!(run_days("run --all").len(), 12);
+    }
+
+    #[test]
+    fn parses_run_flags() {
+        assert_eq!(
+            parse_args("run 1 2 --parallel --time"),
+            Ok(Command::Run {
+                days: vec![1, 2],
+                time: true,
+                example: false,
+                json: false,
+                parallel: true,
+                input: None,
+            })
+        );
+    }
+
+    #[test]
+    fn parses_watch_interval() {
+        assert_eq!(
+            parse_args("watch 5 --example --interval 100"),
+            Ok(Command::Watch {
+                days: vec![5],
+                example: true,
+                interval: Duration::from_millis(100),
+            })
+        );
+        assert_eq!(
+            parse_args("watch 5 --interval 0"),
+            Err(CliError::InvalidValue(
+                "--interval".to_string(),
+                "0".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn fetches_any_day_of_the_calendar() {
+        assert_eq!(
+            parse_args("fetch 20..=21 --session abc"),
+            Ok(Command::Fetch {
+                days: vec![20, 21],
+                url: None,
+                session: Some("abc".to_string()),
+            })
+        );
+        assert_eq!(parse_args("fetch 26"), Err(CliError::UnknownDay(26)));
+    }
+
+    #[test]
+    fn ranks_any_amount_of_elves() {
+        assert_eq!(
+            parse_args("elves --top 5 --input - --lenient"),
+            Ok(Command::Elves {
+                example: false,
+                input: Some(PathBuf::from("-")),
+                top: 5,
+                lenient: true,
+            })
+        );
+        assert!(matches!(
+            parse_args("elves --example"),
+            Ok(Command::Elves {
+                top: 3,
+                lenient: false,
+                ..
+            })
+        ));
+        assert_eq!(
+            parse_args("elves --top 0"),
+            Err(CliError::InvalidValue("--top".to_string(), "0".to_string()))
+        );
+    }
+
+    #[test]
+    fn parses_guide_letters() {
+        let args = [
+            "guide",
+            "--example",
+            "--responses",
+            "X=paper, Y=rock, Z=scissors",
+        ]
+        .map(String::from);
+        assert_eq!(
+            parse(&args, &AVAILABLE),
+            Ok(Command::Guide {
+                example: true,
+                input: None,
+                rules: None,
+                letters: Letters {
+                    responses: Some("X=paper, Y=rock, Z=scissors".to_string()),
+                    ..Letters::default()
+                },
+            })
+        );
+        assert_eq!(
+            parse_args("guide 2"),
+            Err(CliError::UnexpectedArgument("2".to_string()))
+        );
+        assert!(matches!(
+            parse_args("guide --rules data/day_2.rpsls.rules"),
+            Ok(Command::Guide { rules: Some(path), .. }) if path.ends_with("day_2.rpsls.rules")
+        ));
+        assert_eq!(
+            parse_args("guide --states"),
+            Err(CliError::MissingValue("--states".to_string()))
+        );
+    }
+
+    #[test]
+    fn creates_only_new_days() {
+        assert_eq!(parse_args("new-day 13"), Ok(Command::NewDay(13)));
+        assert_eq!(parse_args("new-day 12"), Err(CliError::DayExists(12)));
+        assert_eq!(
+            parse_args("new-day 26"),
+            Err(CliError::InvalidDay("26".to_string()))
+        );
+        assert_eq!(parse_args("new-day"), Err(CliError::MissingDays));
+    }
+
+    #[test]
+    fn rejects_unknown_days_and_flags() {
+        assert_eq!(parse_args("run 13"), Err(CliError::UnknownDay(13)));
+        assert_eq!(
+            parse_args("run 5..1"),
+            Err(CliError::InvalidDay("5..1".to_string()))
+        );
+        assert_eq!(parse_args("run"), Err(CliError::MissingDays));
+        assert_eq!(
+            parse_args("run 1 --fast"),
+            Err(CliError::UnknownFlag("--fast".to_string()))
+        );
+        assert_eq!(
+            parse_args("bench 1 --runs"),
+            Err(CliError::MissingValue("--runs".to_string()))
+        );
+        assert_eq!(
+            parse_args("run 1 2 --input -"),
+            Err(CliError::InputNeedsOneDay)
+        );
+    }
+}
@@ -1,43 +1,452 @@
-fn split_by_line(s: &str) -> Vec<&str> {
-    s.split("\n").collect::<Vec<&str>>()
+use std::{
+    cmp::Reverse,
+    collections::{BTreeMap, BinaryHeap},
+    fmt,
+    io::BufRead,
+};
+
+use crate::{
+    error::{Error, LineError, Result},
+    parse,
+    solution::Solution,
+};
+
+const DAY: u8 = 1;
+
+/// How to treat lines that do not hold calories.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Mode {
+    /// Fails on the first invalid line.
+    #[default]
+    Strict,
+    /// Skips invalid lines, keeping track of them. Lines with only whitespace separate elves.
+    Lenient,
+}
+
+/// A line skipped in lenient mode.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Skipped {
+    /// 1-based line of the input.
+    pub line: usize,
+    pub error: LineError,
+}
+
+impl fmt::Display for Skipped {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}, {}", self.line, self.error)
+    }
+}
+
+/// Reads the calories on a line, `None` being the empty line between two elves.
+fn parse_calories(line: &str) -> std::result::Result<Option<u64>, LineError> {
+    if line.is_empty() {
+        return Ok(None);
+    }
+
+    if line.trim().is_empty() {
+        return Err(LineError::new(1, line, "separator holds whitespace"));
+    }
+
+    parse::line(line, |cursor| {
+        if cursor.rest().starts_with('-') {
+            return Err(cursor.error("negative calories"));
+        }
+        cursor.uint().map(Some)
+    })
+}
+
+/// Calories carried by each elf, in the order of the input.
+pub fn calculate_elfs(input: &str) -> Result<Vec<u64>> {
+    Elfs::new(input.as_bytes(), Mode::Strict).collect()
+}
+
+/// Calories carried by each elf, read one line at a time so inputs of any size fit in memory.
+pub struct Elfs<R> {
+    reader: R,
+    mode: Mode,
+    line: String,
+    number: usize,
+    skipped: Vec<Skipped>,
+    failed: bool,
+}
+
+impl<R: BufRead> Elfs<R> {
+    pub fn new(reader: R, mode: Mode) -> Self {
+        Self {
+            reader,
+            mode,
+            line: String::new(),
+            number: 0,
+            skipped: Vec::new(),
+            failed: false,
+        }
+    }
+
+    /// The lines skipped so far, always empty in strict mode.
+    pub fn skipped(&self) -> &[Skipped] {
+        &self.skipped
+    }
+
+    pub fn into_skipped(self) -> Vec<Skipped> {
+        self.skipped
+    }
+
+    fn fail(&mut self, error: LineError) -> Option<Result<u64>> {
+        self.failed = true;
+        Some(Err(error.at(DAY, self.number)))
+    }
 }
 
-fn calculate_elfs(s: &Vec<&str>) -> Vec<i32> {
-    let mut numbers = Vec::new();
-    let mut cursor = 0;
+impl<R: BufRead> Iterator for Elfs<R> {
+    type Item = Result<u64>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut total = None;
+
+        while !self.failed {
+            self.line.clear();
+            self.number += 1;
+
+            match self.reader.read_line(&mut self.line) {
+                Ok(0) => break,
+                Ok(_) => {}
+                Err(error) => return self.fail(LineError::new(1, "", error.to_string())),
+            }
+
+            let line = self.line.trim_end_matches(['\n', '\r']);
+            let calories = match (parse_calories(line), self.mode) {
+                (Ok(calories), _) => calories,
+                (Err(error), Mode::Strict) => return self.fail(error),
+                (Err(error), Mode::Lenient) => {
+                    let separator = error.text.trim().is_empty();
+                    self.skipped.push(Skipped {
+                        line: self.number,
+                        error,
+                    });
+
+                    if !separator {
+                        continue;
+                    }
+                    None
+                }
+            };
 
-    for x in s {
-        if x == &"" {
-            numbers.push(cursor);
-            cursor = 0;
-        } else {
-            if let Ok(n) = x.parse::<i32>() {
-                cursor += n;
+            let Some(calories) = calories else {
+                if total.is_some() {
+                    break;
+                }
+                continue;
+            };
+
+            match total.unwrap_or(0u64).checked_add(calories) {
+                Some(sum) => total = Some(sum),
+                None => {
+                    let error = LineError::new(1, line, "total calories overflow");
+                    return self.fail(error);
+                }
             }
         }
+
+        total.map(Ok)
+    }
+}
+
+/// The largest `n` values seen so far, kept in a min-heap of at most `n` elements.
+#[derive(Debug, Clone)]
+pub struct TopN<T> {
+    heap: BinaryHeap<Reverse<T>>,
+    n: usize,
+}
+
+impl<T: Ord> TopN<T> {
+    pub fn new(n: usize) -> Self {
+        Self {
+            heap: BinaryHeap::with_capacity(n + 1),
+            n,
+        }
+    }
+
+    pub fn push(&mut self, value: T) {
+        self.heap.push(Reverse(value));
+        if self.heap.len() > self.n {
+            self.heap.pop();
+        }
     }
 
-    numbers.push(cursor);
-    numbers
+    /// The kept values, largest first.
+    pub fn into_sorted_vec(self) -> Vec<T> {
+        // Sorting the reversed values ascending puts the largest first
+        self.heap
+            .into_sorted_vec()
+            .into_iter()
+            .map(|Reverse(value)| value)
+            .collect()
+    }
+}
+
+/// The `n` largest totals in `reader`, largest first, in constant memory.
+pub fn top_elfs(reader: impl BufRead, n: usize) -> Result<Vec<u64>> {
+    let mut top = TopN::new(n);
+    for total in Elfs::new(reader, Mode::Strict) {
+        top.push(total?);
+    }
+    Ok(top.into_sorted_vec())
 }
 
-pub fn run_1(input: String) {
-    let elfs = split_by_line(&input);
+/// Every elf along with the calories they carry. Elves are numbered from 1, in input order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Inventory {
+    totals: Vec<u64>,
+    sorted: Vec<u64>,
+}
 
-    let mut elfs_amounts = calculate_elfs(&elfs);
+impl Inventory {
+    pub fn new(totals: Vec<u64>) -> Self {
+        let mut sorted = totals.clone();
+        sorted.sort_unstable();
+        Self { totals, sorted }
+    }
 
-    elfs_amounts.sort();
+    /// Reads every elf, along with the lines skipped in lenient mode.
+    pub fn from_reader(reader: impl BufRead, mode: Mode) -> Result<(Self, Vec<Skipped>)> {
+        let mut elfs = Elfs::new(reader, mode);
+        let totals = elfs.by_ref().collect::<Result<_>>()?;
+        Ok((Self::new(totals), elfs.into_skipped()))
+    }
 
-    let maximum = elfs_amounts.last();
+    pub fn len(&self) -> usize {
+        self.totals.len()
+    }
 
-    println!("Day 1: ");
+    pub fn is_empty(&self) -> bool {
+        self.totals.is_empty()
+    }
 
-    if let Some(maximum) = maximum {
-        println!("  Part 1: {}", maximum);
+    /// Calories carried by the given elf.
+    pub fn carried_by(&self, elf: usize) -> Option<u64> {
+        self.totals.get(elf.checked_sub(1)?).copied()
     }
 
-    // take last 3
-    let last_3 = elfs_amounts.split_off(elfs_amounts.len() - 3);
-    let total = last_3.iter().sum::<i32>();
-    println!("  Part 2: {}", total);
+    /// Elves and their totals, in input order.
+    pub fn elves(&self) -> impl Iterator<Item = (usize, u64)> + '_ {
+        (1..).zip(self.totals.iter().copied())
+    }
+
+    /// The `n` elves carrying the most, largest first. Ties go to the elf found first, and fewer
+    /// than `n` elves are returned when there are not that many.
+    pub fn top(&self, n: usize) -> Vec<(usize, u64)> {
+        let mut top = TopN::new(n);
+        for (elf, total) in self.elves() {
+            top.push((total, Reverse(elf)));
+        }
+
+        top.into_sorted_vec()
+            .into_iter()
+            .map(|(total, Reverse(elf))| (elf, total))
+            .collect()
+    }
+
+    pub fn mean(&self) -> Option<f64> {
+        let sum = self.totals.iter().map(|total| *total as u128).sum::<u128>();
+        (!self.is_empty()).then(|| sum as f64 / self.len() as f64)
+    }
+
+    /// The middle total, or the mean of the two middle ones for an even amount of elves.
+    pub fn median(&self) -> Option<f64> {
+        let middle = self.len() / 2;
+        match self.len() {
+            0 => None,
+            length if length % 2 == 1 => Some(self.sorted[middle] as f64),
+            _ => Some((self.sorted[middle - 1] as f64 + self.sorted[middle] as f64) / 2.0),
+        }
+    }
+
+    /// The smallest total that at least `percent` of the elves carry no more than, with `percent`
+    /// between 0 and 100.
+    pub fn percentile(&self, percent: f64) -> Option<u64> {
+        if self.is_empty() || !(0.0..=100.0).contains(&percent) {
+            return None;
+        }
+
+        // Nearest rank: the first rank covering the requested share of elves
+        let rank = (percent / 100.0 * self.len() as f64).ceil() as usize;
+        Some(self.sorted[rank.clamp(1, self.len()) - 1])
+    }
+
+    /// Totals carried by more than one elf, largest first, along with those elves.
+    pub fn ties(&self) -> Vec<(u64, Vec<usize>)> {
+        let mut elves = BTreeMap::<u64, Vec<usize>>::new();
+        for (elf, total) in self.elves() {
+            elves.entry(total).or_default().push(elf);
+        }
+
+        elves
+            .into_iter()
+            .rev()
+            .filter(|(_, elves)| elves.len() > 1)
+            .collect()
+    }
+}
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    /// The three largest totals, largest first.
+    type Input = Vec<u64>;
+    type PartOne = u64;
+    type PartTwo = u64;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        top_elfs(input.as_bytes(), 3)
+    }
+
+    fn part_one(top: &Self::Input) -> Result<Self::PartOne> {
+        Ok(top.first().copied().unwrap_or_default())
+    }
+
+    fn part_two(top: &Self::Input) -> Result<Self::PartTwo> {
+        top.iter()
+            .try_fold(0u64, |sum, total| sum.checked_add(*total))
+            .ok_or_else(|| Error::no_solution(DAY, 2, "the three largest totals overflow"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::solve;
+
+    const EXAMPLE: &str = include_str!("../../data/day_1.example.txt");
+
+    #[test]
+    fn calculates_every_elf() {
+        assert_eq!(
+            calculate_elfs(EXAMPLE).unwrap(),
+            vec![6000, 4000, 11000, 24000, 10000]
+        );
+    }
+
+    #[test]
+    fn reads_crlf_inputs() {
+        assert_eq!(
+            calculate_elfs("1000\r\n2000\r\n\r\n3000\r\n\r\n").unwrap(),
+            vec![3000, 3000]
+        );
+    }
+
+    #[test]
+    fn part_two_with_fewer_than_three_elfs() {
+        let elfs = Day1::parse("1000\n\n2000").unwrap();
+        assert_eq!(Day1::part_two(&elfs).unwrap(), 3000);
+    }
+
+    #[test]
+    fn streams_the_largest_totals() {
+        assert_eq!(top_elfs(EXAMPLE.as_bytes(), 2).unwrap(), vec![24000, 11000]);
+        assert_eq!(top_elfs(EXAMPLE.as_bytes(), 9).unwrap().len(), 5);
+        assert!(top_elfs(EXAMPLE.as_bytes(), 0).unwrap().is_empty());
+        assert!(top_elfs(&b"\n\n"[..], 3).unwrap().is_empty());
+    }
+
+    #[test]
+    fn reports_overflowing_totals() {
+        let input = format!("1\n\n{}\n1\n", u64::MAX);
+        let error = top_elfs(input.as_bytes(), 3).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "day 1, line 4, column 1: total calories overflow in `1`"
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_lines_in_strict_mode() {
+        let error = |input: &str| calculate_elfs(input).unwrap_err().to_string();
+        assert_eq!(
+            error("1\n2x\n"),
+            "day 1, line 2, column 2: unexpected `x` in `2x`"
+        );
+        assert_eq!(
+            error("1\n\n-5\n"),
+            "day 1, line 3, column 1: negative calories in `-5`"
+        );
+        assert_eq!(
+            error("1\n \n2\n"),
+            "day 1, line 2, column 1: separator holds whitespace in ` `"
+        );
+        assert_eq!(
+            error("1\n99999999999999999999\n"),
+            "day 1, line 2, column 1: number too large in `99999999999999999999`"
+        );
+    }
+
+    #[test]
+    fn reports_skipped_lines_in_lenient_mode() {
+        let input = "1\nabc\n2\n\t\n-3\n4\n";
+        let (inventory, skipped) = Inventory::from_reader(input.as_bytes(), Mode::Lenient).unwrap();
+        assert_eq!(inventory.elves().collect::<Vec<_>>(), vec![(1, 3), (2, 4)]);
+
+        let skipped = skipped.iter().map(ToString::to_string).collect::<Vec<_>>();
+        assert_eq!(
+            skipped,
+            vec![
+                "line 2, column 1: expected a number in `abc`",
+                "line 4, column 1: separator holds whitespace in `\t`",
+                "line 5, column 1: negative calories in `-3`",
+            ]
+        );
+    }
+
+    #[test]
+    fn tracks_which_elf_carries_what() {
+        let inventory = Inventory::from_reader(EXAMPLE.as_bytes(), Mode::Strict)
+            .unwrap()
+            .0;
+        assert_eq!(inventory.len(), 5);
+        assert_eq!(inventory.carried_by(4), Some(24000));
+        assert_eq!(inventory.carried_by(0), None);
+        assert_eq!(inventory.carried_by(6), None);
+        assert_eq!(inventory.top(2), vec![(4, 24000), (3, 11000)]);
+        assert_eq!(inventory.top(9).len(), 5);
+    }
+
+    #[test]
+    fn describes_the_totals() {
+        let inventory = Inventory::from_reader(EXAMPLE.as_bytes(), Mode::Strict)
+            .unwrap()
+            .0;
+        assert_eq!(inventory.mean(), Some(11000.0));
+        assert_eq!(inventory.median(), Some(10000.0));
+        assert_eq!(inventory.percentile(0.0), Some(4000));
+        assert_eq!(inventory.percentile(50.0), Some(10000));
+        assert_eq!(inventory.percentile(100.0), Some(24000));
+        assert_eq!(inventory.percentile(101.0), None);
+
+        let inventory = Inventory::new(vec![3, 1]);
+        assert_eq!(inventory.median(), Some(2.0));
+    }
+
+    #[test]
+    fn reports_ties() {
+        let inventory = Inventory::new(vec![5, 7, 5, 7, 1, 7]);
+        assert_eq!(inventory.ties(), vec![(7, vec![2, 4, 6]), (5, vec![1, 3])]);
+        assert_eq!(inventory.top(2), vec![(2, 7), (4, 7)]);
+        assert!(Inventory::new(vec![1, 2]).ties().is_empty());
+    }
+
+    #[test]
+    fn handles_empty_inventories() {
+        let inventory = Inventory::new(Vec::new());
+        assert!(inventory.is_empty());
+        assert!(inventory.top(3).is_empty());
+        assert_eq!(inventory.mean(), None);
+        assert_eq!(inventory.median(), None);
+        assert_eq!(inventory.percentile(50.0), None);
+    }
+
+    #[test]
+    fn solves_example() {
+        let solved = solve::<Day1>(EXAMPLE).unwrap();
+        assert_eq!(solved.part_one, 24000.into());
+        assert_eq!(solved.part_two, 45000.into());
+    }
 }
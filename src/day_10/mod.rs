@@ -1,7 +1,17 @@
 use std::collections::BTreeMap;
 
+use crate::{
+    error::{LineError, Result},
+    grid::Grid,
+    parse::{self, words},
+    point::Point,
+    solution::{Answer, Solution},
+};
+
+const DAY: u8 = 10;
+
 #[derive(Debug)]
-enum Command {
+pub enum Command {
     Noop,
     Addx(i32),
 }
@@ -13,8 +23,8 @@ struct CycleTracker {
     adx: Option<i32>,
 }
 
-impl From<Command> for CycleTracker {
-    fn from(value: Command) -> Self {
+impl From<&Command> for CycleTracker {
+    fn from(value: &Command) -> Self {
         match value {
             Command::Noop => Self {
                 execute_at: 1,
@@ -24,7 +34,7 @@ impl From<Command> for CycleTracker {
             Command::Addx(adx) => Self {
                 execute_at: 2,
                 current_cycle: 0,
-                adx: Some(adx),
+                adx: Some(*adx),
             },
         }
     }
@@ -41,25 +51,27 @@ impl CycleTracker {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum CrtPixel {
+pub enum CrtPixel {
     Empty,
     Lit,
     Dark,
 }
 
-impl ToString for CrtPixel {
-    fn to_string(&self) -> String {
+impl std::fmt::Display for CrtPixel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            CrtPixel::Empty => " ".to_string(),
-            CrtPixel::Lit => "█".to_string(),
-            CrtPixel::Dark => "░".to_string(),
+            CrtPixel::Empty => write!(f, " "),
+            CrtPixel::Lit => write!(f, "█"),
+            CrtPixel::Dark => write!(f, "░"),
         }
     }
 }
 
-struct Crt {
-    screen: [[CrtPixel; 40]; 6],
-    sprite: [CrtPixel; 40],
+/// The 40x6 screen drawn by the CPU, one pixel per cycle.
+pub struct Crt {
+    screen: Grid<CrtPixel>,
+    /// Column of the middle of the three pixels wide sprite, which may be off the screen.
+    sprite: i32,
     current_pixel: usize,
 }
 
@@ -68,86 +80,77 @@ fn pixels_to_string(pixels: &[CrtPixel]) -> String {
 }
 
 impl Crt {
-    fn new() -> Self {
-        let mut crt = Self {
-            screen: [[CrtPixel::Empty; 40]; 6],
-            sprite: [CrtPixel::Dark; 40],
+    pub fn new() -> Self {
+        Self {
+            screen: Grid::new(40, 6, CrtPixel::Empty),
+            sprite: 1,
             current_pixel: 0,
-        };
-
-        crt.set_sprite(1);
-
-        crt
-    }
-
-    fn set_sprite(&mut self, position: usize) {
-        if position > 40 || position == 0 {
-            panic!("Invalid position: {}", position);
         }
+    }
 
-        let position = position - 1;
+    /// Moves the sprite to the value of the register.
+    pub fn set_sprite(&mut self, register: i32) {
+        self.sprite = register;
+    }
 
-        let mut new_sprite = [CrtPixel::Dark; 40];
+    /// Draws the pixel under the beam, ignoring the ones past the end of the screen.
+    pub fn draw_next_pixel(&mut self) {
+        let row = self.current_pixel / self.screen.width();
+        let column = self.current_pixel % self.screen.width();
 
-        for i in 0..3 {
-            let pos = position + i;
-            if pos < 40 {
-                new_sprite[pos] = CrtPixel::Lit;
-            }
+        if let Some(pixel) = self.screen.get_mut(Point::new(column as i64, row as i64)) {
+            let lit = (column as i64 - self.sprite as i64).abs() <= 1;
+            *pixel = if lit { CrtPixel::Lit } else { CrtPixel::Dark };
         }
 
-        self.sprite = new_sprite;
+        self.current_pixel += 1;
     }
+}
 
-    fn draw_next_pixel(&mut self) {
-        // Maybe this should be done we a track for the column.
-        // Magic maths for now I guess
-
-        let screen_index = (self.current_pixel as f64 / 40_f64).floor() as usize;
-        let pixel_index = self.current_pixel % 40;
-
-        let pixel = self.sprite[pixel_index];
-        self.screen[screen_index][pixel_index] = pixel;
-
-        self.current_pixel += 1;
+impl Default for Crt {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
 impl std::fmt::Display for Crt {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for row in self.screen.iter() {
-            writeln!(f, "{}", pixels_to_string(row))?;
-        }
-
-        Ok(())
+        writeln!(f, "{}", self.screen)
     }
 }
 
-fn parse_line(line: &str) -> Command {
-    let mut split = line.split_whitespace();
-
-    let command = split.next().unwrap();
-
-    let cmd = match command {
-        "noop" => Some(Command::Noop),
-        "addx" => split.next().map(|n| Command::Addx(n.parse().unwrap())),
-        _ => None,
-    };
-
-    if let Some(cmd) = cmd {
-        cmd
-    } else {
-        panic!("Invalid command: {}", command);
+impl From<Crt> for Answer {
+    fn from(crt: Crt) -> Self {
+        Answer::Image(crt.screen.rows().map(pixels_to_string).collect())
     }
 }
 
-pub fn run(input: String) {
-    let commands = input.lines().map(parse_line).collect::<Vec<_>>();
+pub fn parse_line(line: &str) -> std::result::Result<Command, LineError> {
+    match words(line).as_slice() {
+        [(_, "noop")] => Ok(Command::Noop),
+        [(_, "addx"), (column, n)] => n
+            .parse()
+            .map(Command::Addx)
+            .map_err(|_| LineError::new(*column, line, "invalid number")),
+        [(_, "addx")] => Err(LineError::new(
+            line.len() + 1,
+            line,
+            "missing the value to add",
+        )),
+        [(_, "noop" | "addx"), .., (column, _)] => {
+            Err(LineError::new(*column, line, "unexpected argument"))
+        }
+        [(column, _), ..] => Err(LineError::new(*column, line, "invalid command")),
+        [] => Err(LineError::new(1, line, "empty line")),
+    }
+}
 
+/// Runs the program, returning the sum of the signal strengths and the drawn screen.
+pub fn execute(commands: &[Command]) -> (i32, Crt) {
     let mut register = 1;
     let mut cycle_tracker: Option<CycleTracker> = None;
 
-    let mut commands = commands.into_iter();
+    let mut commands = commands.iter();
 
     // We use BTreemap to keep the order of the keys - I want the order just because yes
     let mut strength_tracker =
@@ -186,14 +189,105 @@ pub fn run(input: String) {
                     register += adx;
                 }
 
-                crt.set_sprite(register.try_into().unwrap_or(1));
+                crt.set_sprite(register);
                 cycle_tracker = None;
             }
         } // End of the cycle
     }
 
-    println!("Day 10:");
-    let part_1: i32 = strength_tracker.values().sum();
-    println!("  Part 1: {}", part_1);
-    println!("  Part 2:\n{}", crt);
+    (strength_tracker.values().sum(), crt)
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input = Vec<Command>;
+    type PartOne = i32;
+    type PartTwo = Crt;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse::each_line(DAY, input, parse_line)
+    }
+
+    fn part_one(commands: &Self::Input) -> Result<Self::PartOne> {
+        Ok(execute(commands).0)
+    }
+
+    fn part_two(commands: &Self::Input) -> Result<Self::PartTwo> {
+        Ok(execute(commands).1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::solve;
+
+    const EXAMPLE: &str = include_str!("../../data/day_10.example.txt");
+
+    #[test]
+    fn parses_commands() {
+        assert!(matches!(parse_line("noop"), Ok(Command::Noop)));
+        assert!(matches!(parse_line("addx -5"), Ok(Command::Addx(-5))));
+        assert_eq!(parse_line("addx").unwrap_err().column, 5);
+        assert_eq!(parse_line("addx 1 2").unwrap_err().column, 8);
+        assert_eq!(parse_line("jump 3").unwrap_err().column, 1);
+    }
+
+    #[test]
+    fn draws_the_sprite_around_the_register() {
+        let mut crt = Crt::new();
+        crt.set_sprite(3);
+        for _ in 0..5 {
+            crt.draw_next_pixel();
+        }
+
+        let first_row = crt.to_string().lines().next().unwrap().to_string();
+        assert!(first_row.starts_with("░░███"));
+    }
+
+    fn first_row(register: i32) -> String {
+        let mut crt = Crt::new();
+        crt.set_sprite(register);
+        for _ in 0..40 {
+            crt.draw_next_pixel();
+        }
+        crt.to_string().lines().next().unwrap().to_string()
+    }
+
+    #[test]
+    fn draws_sprites_at_the_edges_and_off_the_screen() {
+        assert_eq!(first_row(0), format!("██{}", "░".repeat(38)));
+        assert_eq!(first_row(-1), format!("█{}", "░".repeat(39)));
+        assert_eq!(first_row(-5), "░".repeat(40));
+        assert_eq!(first_row(40), format!("{}█", "░".repeat(39)));
+        assert_eq!(first_row(61), "░".repeat(40));
+    }
+
+    #[test]
+    fn runs_programs_moving_the_sprite_anywhere() {
+        for program in ["addx -1\nnoop", "addx -30\nnoop\nnoop", "addx 60\nnoop"] {
+            let solved = solve::<Day10>(program).unwrap();
+            assert!(matches!(solved.part_two, Answer::Image(_)));
+        }
+
+        let solved = solve::<Day10>("addx -1\nnoop").unwrap();
+        let Answer::Image(rows) = solved.part_two else {
+            panic!("expected an image");
+        };
+        assert!(rows[0].starts_with("██░"));
+    }
+
+    #[test]
+    fn solves_example() {
+        let solved = solve::<Day10>(EXAMPLE).unwrap();
+        assert_eq!(solved.part_one, 13140.into());
+
+        let Answer::Image(rows) = solved.part_two else {
+            panic!("expected an image");
+        };
+        assert_eq!(rows[0], "██░░".repeat(10));
+        let second_row = "███░░░".repeat(7).chars().take(40).collect::<String>();
+        assert_eq!(rows[1], second_row);
+    }
 }
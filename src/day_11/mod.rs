@@ -1,13 +1,22 @@
-use std::collections::VecDeque;
+use std::{collections::VecDeque, str::FromStr};
+
+use crate::{
+    error::{Error, LineError, Result},
+    parse::{self, Cursor},
+    solution::Solution,
+};
+
+const DAY: u8 = 11;
+const UNCOMPUTABLE: &str = "a worry level overflows, goes below zero or is divided by zero";
 
 #[derive(Debug, Default, Clone)]
-struct TestResult {
+pub struct TestResult {
     is_true: usize,
     is_false: usize,
 }
 
 #[derive(Debug, Clone)]
-enum OperationType {
+pub enum OperationType {
     Add(i64, i64),
     Multiply(i64, i64),
     Divide(i64, i64),
@@ -20,7 +29,7 @@ impl Default for OperationType {
     }
 }
 
-fn execute_operation(t: (i64, i64, u64), f: impl FnOnce(u64, u64) -> u64) -> u64 {
+fn execute_operation(t: (i64, i64, u64), f: impl FnOnce(u64, u64) -> Option<u64>) -> Option<u64> {
     let with = t.2;
     let a = t.0.try_into().unwrap_or(with);
     let b = t.1.try_into().unwrap_or(with);
@@ -28,18 +37,19 @@ fn execute_operation(t: (i64, i64, u64), f: impl FnOnce(u64, u64) -> u64) -> u64
 }
 
 impl OperationType {
-    fn execute(&self, old: u64) -> u64 {
+    /// The new worry level, `None` when it overflows, goes below zero or divides by zero.
+    pub fn execute(&self, old: u64) -> Option<u64> {
         match self {
-            OperationType::Add(a, b) => execute_operation((*a, *b, old), |a, b| a + b),
-            OperationType::Multiply(a, b) => execute_operation((*a, *b, old), |a, b| a * b),
-            OperationType::Divide(a, b) => execute_operation((*a, *b, old), |a, b| a / b),
-            OperationType::Subtract(a, b) => execute_operation((*a, *b, old), |a, b| a - b),
+            OperationType::Add(a, b) => execute_operation((*a, *b, old), u64::checked_add),
+            OperationType::Multiply(a, b) => execute_operation((*a, *b, old), u64::checked_mul),
+            OperationType::Divide(a, b) => execute_operation((*a, *b, old), u64::checked_div),
+            OperationType::Subtract(a, b) => execute_operation((*a, *b, old), u64::checked_sub),
         }
     }
 }
 
 #[derive(Debug, Default, Clone)]
-struct Monkey {
+pub struct Monkey {
     items: VecDeque<u64>,
     operation: OperationType,
     test: u8,
@@ -48,15 +58,19 @@ struct Monkey {
 }
 
 impl Monkey {
-    fn get_monkey_destination(&self, worry_level: u64) -> usize {
-        if &worry_level % self.test as u64 == 0 {
+    pub fn get_monkey_destination(&self, worry_level: u64) -> usize {
+        if worry_level % self.test as u64 == 0 {
             self.test_result.is_true
         } else {
             self.test_result.is_false
         }
     }
 
-    fn inspect_next(&mut self) -> Option<u64> {
+    pub fn inspection_amount(&self) -> u64 {
+        self.inspection_amount
+    }
+
+    pub fn inspect_next(&mut self) -> Option<u64> {
         let next = self.items.pop_front();
         if next.is_some() {
             self.inspection_amount += 1;
@@ -65,105 +79,129 @@ impl Monkey {
     }
 }
 
-fn parse_last_char(line: &str) -> u8 {
-    let s = line
-        .chars()
-        .rev()
-        .take_while(|x| x.is_numeric())
-        .fold(String::new(), |mut acc, s| {
-            acc.insert(0, s);
-            acc
-        });
+fn parse_items(line: &str) -> std::result::Result<VecDeque<u64>, LineError> {
+    parse::line(line, |cursor| {
+        cursor.key("Starting items")?;
+        if cursor.is_done() {
+            return Ok(VecDeque::new());
+        }
 
-    s.parse().unwrap()
+        cursor
+            .list(", ", |cursor| cursor.uint())
+            .map(VecDeque::from)
+    })
 }
 
-fn parse_block(block: &Vec<String>) -> Monkey {
-    if block.len() < 6 {
-        panic!("Block too short");
+// `old` is stored as -1 and replaced by the worry level when executed
+fn parse_operand(cursor: &mut Cursor) -> std::result::Result<i64, LineError> {
+    if cursor.eat("old") {
+        Ok(-1)
+    } else {
+        cursor.uint()
     }
+}
 
-    let mut monkey = Monkey::default();
+fn parse_operation(line: &str) -> std::result::Result<OperationType, LineError> {
+    parse::line(line, |cursor| {
+        cursor.key("Operation")?;
+        cursor.tag("new = ")?;
+
+        let a = parse_operand(cursor)?;
+        cursor.spaces();
+
+        let column = cursor.column();
+        let operation = match cursor.word()? {
+            "+" => OperationType::Add,
+            "-" => OperationType::Subtract,
+            "*" => OperationType::Multiply,
+            "/" => OperationType::Divide,
+            _ => return Err(cursor.error_at(column, "unknown operation")),
+        };
+        cursor.spaces();
+
+        let column = cursor.column();
+        let b = parse_operand(cursor)?;
+        if matches!(operation(a, b), OperationType::Divide(_, 0)) {
+            return Err(cursor.error_at(column, "cannot divide by zero"));
+        }
 
-    for (index, line) in block.into_iter().skip(1).enumerate() {
-        let line = line.trim();
-        match index {
-            0 => {
-                let (_, numbers) = line.split_once(":").unwrap();
-                let items = numbers
-                    .split(",")
-                    .map(|x| x.trim().parse().unwrap())
-                    .collect();
-                monkey.items = items;
-            }
-            1 => {
-                let (_, operation) = line.split_once("=").unwrap();
-                let operation = operation.trim();
-
-                let mut a = String::new();
-                let mut b = String::new();
-                let mut sign = String::new();
-
-                for c in operation.chars() {
-                    if c.is_whitespace() {
-                        continue;
-                    }
-
-                    if sign.is_empty() {
-                        a.push(c);
-                    } else {
-                        b.push(c);
-                    }
-
-                    if c == '+' || c == '-' || c == '*' || c == '/' {
-                        sign.push(c);
-                    }
-                }
+        Ok(operation(a, b))
+    })
+}
 
-                let parse = |s: String| s.parse().unwrap_or(-1);
+/// Parses a `key: text N` line, ending with a number.
+fn parse_rule<T: FromStr>(line: &str, key: &str, text: &str) -> std::result::Result<T, LineError> {
+    parse::line(line, |cursor| {
+        cursor.key(key)?;
+        cursor.tag(text)?;
+        cursor.uint()
+    })
+}
 
-                monkey.operation = match sign.as_str() {
-                    "+" => OperationType::Add(parse(a), parse(b)),
-                    "-" => OperationType::Subtract(parse(a), parse(b)),
-                    "*" => OperationType::Multiply(parse(a), parse(b)),
-                    "/" => OperationType::Divide(parse(a), parse(b)),
-                    _ => panic!("Unknown operation: {}", sign),
-                };
+/// Parses the six lines describing a monkey, `first_line` being the 1-based line of its header.
+pub fn parse_block(block: &[&str], first_line: usize) -> Result<Monkey> {
+    let [header, items, operation, test, if_true, if_false] = block else {
+        let error = match block.get(6) {
+            Some(extra) => LineError::new(1, extra, "unexpected line in a monkey block")
+                .at(DAY, first_line + 6),
+            None => {
+                LineError::new(1, &block.join(" "), "monkey block too short").at(DAY, first_line)
             }
-            2 => monkey.test = parse_last_char(line),
-            3 => monkey.test_result.is_true = parse_last_char(line) as usize,
-            4 => monkey.test_result.is_false = parse_last_char(line) as usize,
-            _ => {}
-        }
+        };
+        return Err(error);
+    };
+
+    let at = |offset: usize| move |e: LineError| e.at(DAY, first_line + offset);
+
+    parse::line(header, |cursor| {
+        cursor.tag("Monkey ")?;
+        cursor.uint::<usize>()?;
+        cursor.tag(":")
+    })
+    .map_err(at(0))?;
+
+    let monkey = Monkey {
+        items: parse_items(items).map_err(at(1))?,
+        operation: parse_operation(operation).map_err(at(2))?,
+        test: parse_rule(test, "Test", "divisible by ").map_err(at(3))?,
+        test_result: TestResult {
+            is_true: parse_rule(if_true, "If true", "throw to monkey ").map_err(at(4))?,
+            is_false: parse_rule(if_false, "If false", "throw to monkey ").map_err(at(5))?,
+        },
+        inspection_amount: 0,
+    };
+
+    if monkey.test == 0 {
+        let error = LineError::new(test.len(), test, "cannot divide by zero");
+        return Err(at(3)(error));
     }
 
-    monkey
+    Ok(monkey)
 }
 
-fn parse(input: &str) -> Vec<Monkey> {
-    let lines = input.lines();
-
-    let mut current_block = Vec::new();
-    let mut monkeys = Vec::new();
-
-    for line in lines {
-        if line.is_empty() {
-            continue;
-        }
-
-        current_block.push(line.to_string());
-
-        if current_block.len() == 6 {
-            let monkey = parse_block(&current_block);
-            monkeys.push(monkey);
-            current_block.clear();
+pub fn parse(input: &str) -> Result<Vec<Monkey>> {
+    let blocks = parse::blocks(input).collect::<Vec<_>>();
+    let monkeys = blocks
+        .iter()
+        .map(|block| parse_block(&block.lines, block.first_line))
+        .collect::<Result<Vec<_>>>()?;
+
+    for (monkey, block) in monkeys.iter().zip(&blocks) {
+        let results = [monkey.test_result.is_true, monkey.test_result.is_false];
+        for (offset, target) in results.into_iter().enumerate() {
+            if target >= monkeys.len() {
+                let line = block.lines[4 + offset];
+                let error = LineError::new(line.len(), line, "unknown monkey");
+                return Err(error.at(DAY, block.first_line + 4 + offset));
+            }
         }
     }
 
-    monkeys
+    Ok(monkeys)
 }
 
-struct Observer<T>
+/// Plays the given amount of rounds, applying the relief function after every inspection.
+pub struct Observer<T>
 where
     T: Fn(u64) -> u64,
 {
@@ -175,45 +213,233 @@ impl<T> Observer<T>
 where
     T: Fn(u64) -> u64,
 {
-    fn new(rounds: usize, relif: T) -> Self {
+    pub fn new(rounds: usize, relif: T) -> Self {
         Self { rounds, relif }
     }
 
-    fn observe(&self, mut monkeys: Vec<Monkey>) -> u64 {
+    /// The monkey business after every round, `None` when a worry level cannot be computed.
+    pub fn observe(&self, mut monkeys: Vec<Monkey>) -> Option<u64> {
         for _ in 0..self.rounds {
             for monkey_index in 0..monkeys.len() {
                 while let Some(prev) = monkeys[monkey_index].inspect_next() {
                     let monkey = &monkeys[monkey_index];
 
-                    let worry_level = monkey.operation.execute(prev);
+                    let worry_level = monkey.operation.execute(prev)?;
                     let worry_level = (self.relif)(worry_level);
                     let next_monkey = monkey.get_monkey_destination(worry_level);
 
                     monkeys[next_monkey].items.push_back(worry_level);
                 }
-                // println!("Monkeys: {:?}", monkeys);
             }
         }
 
-        monkeys.sort_by(|a, b| b.inspection_amount.cmp(&a.inspection_amount));
+        monkeys.sort_by_key(|x| std::cmp::Reverse(x.inspection_amount));
+
+        Some(
+            monkeys
+                .into_iter()
+                .map(|x| x.inspection_amount)
+                .take(2)
+                .product(),
+        )
+    }
+}
+
+fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+/// The least common multiple of every divisor, or `None` when it does not fit in a `u64`.
+pub fn common_multiple(monkeys: &[Monkey]) -> Option<u64> {
+    monkeys.iter().try_fold(1u64, |lcm, monkey| {
+        let test = monkey.test as u64;
+        lcm.checked_mul(test / gcd(lcm, test))
+    })
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input = Vec<Monkey>;
+    type PartOne = u64;
+    type PartTwo = u64;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse(input)
+    }
 
-        monkeys
-            .into_iter()
-            .map(|x| x.inspection_amount)
-            .take(2)
-            .product()
+    fn part_one(monkeys: &Self::Input) -> Result<Self::PartOne> {
+        Observer::new(20, |x| x / 3)
+            .observe(monkeys.clone())
+            .ok_or_else(|| Error::no_solution(DAY, 1, UNCOMPUTABLE))
+    }
+
+    fn part_two(monkeys: &Self::Input) -> Result<Self::PartTwo> {
+        let lcm = common_multiple(monkeys)
+            .ok_or_else(|| Error::no_solution(DAY, 2, "the divisors have no common multiple"))?;
+        Observer::new(10_000, |x| x % lcm)
+            .observe(monkeys.clone())
+            .ok_or_else(|| Error::no_solution(DAY, 2, UNCOMPUTABLE))
     }
 }
 
-pub fn run(input: String) {
-    let monkeys = parse(&input);
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::solve;
+
+    const EXAMPLE: &str = include_str!("../../data/day_11.example.txt");
 
-    let part_1 = Observer::new(20, |x| x / 3).observe(monkeys.clone());
+    fn block(lines: &str) -> Vec<&str> {
+        lines.lines().collect()
+    }
+
+    #[test]
+    fn parses_a_monkey() {
+        let lines = block(
+            "Monkey 2:
+  Starting items: 79, 60, 97
+  Operation: new = old * old
+  Test: divisible by 13
+    If true: throw to monkey 1
+    If false: throw to monkey 3",
+        );
+
+        let mut monkey = parse_block(&lines, 1).unwrap();
+        assert_eq!(monkey.items, [79, 60, 97]);
+        assert_eq!(monkey.operation.execute(3), Some(9));
+        assert_eq!(monkey.get_monkey_destination(26), 1);
+        assert_eq!(monkey.get_monkey_destination(27), 3);
+
+        assert_eq!(monkey.inspect_next(), Some(79));
+        assert_eq!(monkey.inspection_amount(), 1);
+    }
 
-    println!("Day 11");
-    println!("  Part 1: {}", part_1);
+    #[test]
+    fn parses_constant_operations() {
+        let lines = block(
+            "Monkey 0:
+  Starting items: 1
+  Operation: new = old + 6
+  Test: divisible by 19
+    If true: throw to monkey 0
+    If false: throw to monkey 0",
+        );
+
+        let monkey = parse_block(&lines, 1).unwrap();
+        assert_eq!(monkey.operation.execute(4), Some(10));
+    }
 
-    let lcm: u64 = monkeys.iter().map(|x| x.test as u64).product();
-    let part_2 = Observer::new(10_000, |x| x % lcm).observe(monkeys);
-    println!("  Part 2: {}", part_2);
+    #[test]
+    fn rejects_dividing_by_zero() {
+        let error = parse(&EXAMPLE.replacen("new = old * 19", "new = old / 0", 1)).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "day 11, line 3, column 26: cannot divide by zero in `  Operation: new = old / 0`"
+        );
+    }
+
+    #[test]
+    fn reports_worry_levels_that_cannot_be_computed() {
+        let monkeys = parse(&EXAMPLE.replacen("new = old * 19", "new = old - 100", 1)).unwrap();
+        assert!(matches!(
+            Day11::part_one(&monkeys),
+            Err(Error::NoSolution {
+                day: 11,
+                part: 1,
+                ..
+            })
+        ));
+
+        let monkeys = parse(&EXAMPLE.replacen("new = old * 19", "new = old / old", 1)).unwrap();
+        let mut monkey = monkeys[0].clone();
+        monkey.items = VecDeque::from([0]);
+        assert!(Observer::new(1, |x| x).observe(vec![monkey]).is_none());
+    }
+
+    #[test]
+    fn finds_the_common_multiple_of_the_divisors() {
+        let monkeys = parse(EXAMPLE).unwrap();
+        assert_eq!(common_multiple(&monkeys), Some(23 * 19 * 13 * 17));
+
+        let mut monkey = monkeys[0].clone();
+        monkey.test = 6;
+        let mut other = monkey.clone();
+        other.test = 4;
+        assert_eq!(common_multiple(&[monkey, other]), Some(12));
+
+        // Nine distinct primes close to 255 multiply past `u64::MAX`
+        let monkeys = [251, 241, 239, 233, 229, 227, 223, 211, 199].map(|test| {
+            let mut monkey = monkeys[0].clone();
+            monkey.test = test;
+            monkey
+        });
+        assert_eq!(common_multiple(&monkeys), None);
+        assert!(matches!(
+            Day11::part_two(&monkeys.to_vec()),
+            Err(Error::NoSolution {
+                day: 11,
+                part: 2,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn reports_the_line_of_invalid_items() {
+        let lines = block(
+            "Monkey 0:
+  Starting items: 1, x
+  Operation: new = old + 6
+  Test: divisible by 19
+    If true: throw to monkey 0
+    If false: throw to monkey 0",
+        );
+
+        let error = parse_block(&lines, 7).unwrap_err();
+        assert!(matches!(
+            error,
+            crate::error::Error::Parse {
+                line: 8,
+                column: 22,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn rejects_monkeys_with_extra_lines() {
+        let input = EXAMPLE.replacen(
+            "    If false: throw to monkey 3\n",
+            "    If false: throw to monkey 3\n    If bored: throw to monkey 1\n",
+            1,
+        );
+
+        let error = parse(&input).unwrap_err();
+        assert!(matches!(
+            error,
+            crate::error::Error::Parse {
+                line: 7,
+                column: 1,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn parses_crlf_inputs() {
+        let monkeys = parse(&EXAMPLE.replace('\n', "\r\n")).unwrap();
+        assert_eq!(monkeys.len(), 4);
+    }
+
+    #[test]
+    fn solves_example() {
+        let solved = solve::<Day11>(EXAMPLE).unwrap();
+        assert_eq!(solved.part_one, 10605.into());
+        assert_eq!(solved.part_two, 2713310158u64.into());
+    }
 }
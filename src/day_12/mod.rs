@@ -1,108 +1,60 @@
-use std::{
-    collections::{BinaryHeap, HashMap, HashSet, VecDeque},
-    vec,
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use crate::{
+    error::{Error, LineError, Result},
+    grid::Grid,
+    point::Point,
+    solution::Solution,
 };
 
+const DAY: u8 = 12;
+
+/// A height map, remembering where the start and the end marks were found.
 #[derive(Debug)]
-struct Land<T> {
-    matrix: Vec<Vec<Option<T>>>,
-    start: (usize, usize),
-    end: (usize, usize),
+pub struct Land {
+    heights: Grid<Mark>,
+    start: Point,
+    end: Point,
 }
 
-impl<T: Clone> Land<T> {
-    fn new() -> Self {
-        Self {
-            matrix: Vec::new(),
-            start: (0, 0),
-            end: (0, 0),
-        }
+impl std::fmt::Display for Land {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.heights)
     }
 }
-impl<T: ToString> ToString for Land<T> {
-    fn to_string(&self) -> String {
-        self.matrix
-            .iter()
-            .map(|row| {
-                row.iter()
-                    .map(|x| x.as_ref().map(|x| x.to_string()).unwrap_or(" ".to_string()))
-                    .collect::<Vec<String>>()
-                    .join("")
-            })
-            .collect::<Vec<String>>()
-            .join("\n")
-    }
-}
-
-impl FromIterator<Vec<Mark>> for Land<Mark> {
-    fn from_iter<T: IntoIterator<Item = Vec<Mark>>>(iter: T) -> Self {
-        iter.into_iter().fold(Land::new(), |mut acc, n| {
-            let mut position = n.iter().filter(|x| matches!(x, Mark::Position(_)));
-
-            while let Some(Mark::Position(position)) = position.next() {
-                let coords = (
-                    acc.matrix.len(),
-                    n.iter().position(|x| x == position).unwrap(),
-                );
-                match position {
-                    Position::Start => acc.start = coords,
-                    Position::End => acc.end = coords,
-                }
-            }
 
-            acc.insert_row(n)
-        })
+impl Land {
+    pub fn neighbors_of(&self, at: Point) -> Vec<Point> {
+        self.heights.neighbours4(at).collect()
     }
-}
-impl<A> Land<A> {
-    fn insert_row(mut self, line: Vec<A>) -> Self {
-        let l = line.into_iter().map(|x| Some(x)).collect();
-        self.matrix.push(l);
 
-        self
+    pub fn start(&self) -> Point {
+        self.start
     }
 
-    fn neighbors_of(&self, row: usize, col: usize) -> Vec<(usize, usize)> {
-        let mut neighbors = vec![];
-
-        if row > 0 {
-            neighbors.push((row - 1, col));
-        }
-
-        if row < self.matrix.len() - 1 {
-            neighbors.push((row + 1, col));
-        }
-
-        if col > 0 {
-            neighbors.push((row, col - 1));
-        }
-
-        if col < self.matrix[0].len() - 1 {
-            neighbors.push((row, col + 1));
-        }
-
-        neighbors
+    pub fn end(&self) -> Point {
+        self.end
     }
 
-    fn get(&self, loc: (usize, usize)) -> Option<&A> {
-        self.matrix[loc.0][loc.1].as_ref()
+    pub fn get(&self, loc: Point) -> Option<&Mark> {
+        self.heights.get(loc)
     }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
-enum Position {
+pub enum Position {
     Start,
     End,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
-enum Mark {
+pub enum Mark {
     Position(Position),
     Elevation(char),
 }
 
 impl Mark {
-    fn cost(&self) -> u8 {
+    pub fn cost(&self) -> u8 {
         match self {
             Mark::Elevation(x) => *x as u8 - b'a',
             Mark::Position(pos) => match pos {
@@ -122,53 +74,54 @@ impl PartialEq<Position> for Mark {
     }
 }
 
-impl ToString for Mark {
-    fn to_string(&self) -> String {
+impl std::fmt::Display for Mark {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            Mark::Position(Position::Start) => "S".to_string(),
-            Mark::Position(Position::End) => "E".to_string(),
-            Mark::Elevation(x) => x.to_string(),
+            Mark::Position(Position::Start) => write!(f, "S"),
+            Mark::Position(Position::End) => write!(f, "E"),
+            Mark::Elevation(x) => write!(f, "{}", x),
         }
     }
 }
 
-fn parse_line_char(char: char) -> Mark {
+fn parse_mark(char: char) -> Option<Mark> {
     match char {
-        'S' => Mark::Position(Position::Start),
-        'E' => Mark::Position(Position::End),
-        x => Mark::Elevation(x),
+        'S' => Some(Mark::Position(Position::Start)),
+        'E' => Some(Mark::Position(Position::End)),
+        x if x.is_ascii_lowercase() => Some(Mark::Elevation(x)),
+        _ => None,
     }
 }
 
-fn parse_line(line: &str) -> Vec<Mark> {
-    line.chars().map(parse_line_char).collect()
-}
+pub fn parse(input: &str) -> Result<Land> {
+    let heights = Grid::parse(DAY, input, |c| parse_mark(c).ok_or("invalid elevation"))?;
 
-fn parse(line: &str) -> Land<Mark> {
-    line.lines().map(|line| parse_line(line)).collect()
-}
+    let count = |mark: Position| heights.iter().filter(|(_, x)| **x == mark).count();
+    let marks = (count(Position::Start), count(Position::End));
 
-pub fn run(input: String) {
-    let land = parse(&input);
+    if marks != (1, 1) {
+        let text = format!("found {} start and {} end marks", marks.0, marks.1);
+        return Err(LineError::new(1, &text, "expected exactly one `S` and one `E`").at(DAY, 1));
+    }
 
+    let find = |mark: Position| heights.find(|x| *x == mark).unwrap_or_default();
+
+    Ok(Land {
+        start: find(Position::Start),
+        end: find(Position::End),
+        heights,
+    })
+}
+
+/// Breadth-first search from every start at once, returning the steps needed to reach the end.
+pub fn shortest_path(land: &Land, starts: Vec<Point>) -> Option<u32> {
     let mut distances = HashMap::new();
     let mut visited = HashSet::new();
     let mut to_visist = VecDeque::new();
 
-    // This algorithm it's bfs
-
-    // part 1
-    distances.insert(land.start, 0);
-    to_visist.push_back((land.start, 0));
-
-    // part 2
-    for x in 0..land.matrix.len() {
-        for y in 0..land.matrix[0].len() {
-            if land.get((x, y)).unwrap().cost() == 0 {
-                distances.insert((x, y), 0);
-                to_visist.push_back(((x, y), 0));
-            }
-        }
+    for start in starts {
+        distances.insert(start, 0);
+        to_visist.push_back((start, 0));
     }
 
     while let Some((u, dc)) = to_visist.pop_front() {
@@ -177,11 +130,10 @@ pub fn run(input: String) {
         }
 
         if u == land.end {
-            println!("Found it! {}", dc);
-            break;
+            return Some(dc);
         }
 
-        for n in land.neighbors_of(u.0, u.1) {
+        for n in land.neighbors_of(u) {
             let nc = land.get(n).unwrap().cost() as u32;
             let uc = land.get(u).unwrap().cost() as u32;
 
@@ -194,4 +146,83 @@ pub fn run(input: String) {
             }
         }
     }
+
+    None
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Input = Land;
+    type PartOne = u32;
+    type PartTwo = u32;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse(input)
+    }
+
+    fn part_one(land: &Self::Input) -> Result<Self::PartOne> {
+        shortest_path(land, vec![land.start])
+            .ok_or_else(|| Error::no_solution(DAY, 1, "the end cannot be reached"))
+    }
+
+    fn part_two(land: &Self::Input) -> Result<Self::PartTwo> {
+        let starts = land
+            .heights
+            .iter()
+            .filter(|(_, mark)| mark.cost() == 0)
+            .map(|(at, _)| at)
+            .collect();
+
+        shortest_path(land, starts)
+            .ok_or_else(|| Error::no_solution(DAY, 2, "the end cannot be reached"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::solve;
+
+    const EXAMPLE: &str = include_str!("../../data/day_12.example.txt");
+
+    #[test]
+    fn finds_start_and_end() {
+        let land = parse(EXAMPLE).unwrap();
+        assert_eq!(land.start(), Point::ORIGIN);
+        assert_eq!(land.end(), Point::new(5, 2));
+        assert_eq!(land.get(Point::new(1, 0)), Some(&Mark::Elevation('a')));
+    }
+
+    #[test]
+    fn neighbors_stay_inside_the_map() {
+        let land = parse(EXAMPLE).unwrap();
+        assert_eq!(
+            land.neighbors_of(Point::ORIGIN),
+            vec![Point::new(0, 1), Point::new(1, 0)]
+        );
+        assert_eq!(
+            land.neighbors_of(Point::new(7, 4)),
+            vec![Point::new(7, 3), Point::new(6, 4)]
+        );
+    }
+
+    #[test]
+    fn requires_a_single_start_and_end() {
+        assert!(parse("SabE\nabcS").is_err());
+        assert!(parse("Sabc").is_err());
+    }
+
+    #[test]
+    fn reports_unreachable_ends() {
+        let land = parse("SazE").unwrap();
+        assert!(Day12::part_one(&land).is_err());
+    }
+
+    #[test]
+    fn solves_example() {
+        let solved = solve::<Day12>(EXAMPLE).unwrap();
+        assert_eq!(solved.part_one, 31.into());
+        assert_eq!(solved.part_two, 29.into());
+    }
 }
@@ -1,143 +1,708 @@
-const DRAW: &i32 = &3;
-const WIN: &i32 = &6;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs,
+    path::Path,
+};
 
-#[derive(Clone, Debug, PartialEq)]
-enum Tool {
-    Rock,
-    Paper,
-    Scissors,
-}
+use crate::{
+    error::{Error, LineError, Result},
+    parse::{self, words, Cursor},
+    solution::Solution,
+};
+
+const DAY: u8 = 2;
+
+/// Rock, paper, scissors, as played by the elves. See [`Rules::parse`] for the format.
+pub const CLASSIC: &str = "\
+shape rock 1
+shape paper 2
+shape scissors 3
+
+rock beats scissors
+paper beats rock
+scissors beats paper
+
+win 6
+draw 3
+lose 0
+";
 
-enum State {
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum State {
+    Lose,
     Draw,
     Win,
-    Lose,
 }
 
 impl State {
-    fn points(&self) -> i32 {
+    pub const ALL: [Self; 3] = [State::Lose, State::Draw, State::Win];
+
+    pub fn name(self) -> &'static str {
         match self {
-            State::Draw => *DRAW,
-            State::Win => *WIN,
-            State::Lose => 0,
+            State::Lose => "lose",
+            State::Draw => "draw",
+            State::Win => "win",
         }
     }
 }
 
-impl From<&str> for State {
-    fn from(s: &str) -> Self {
-        match s {
-            "X" => State::Lose,
-            "Y" => State::Draw,
-            "Z" => State::Win,
-            _ => panic!("Invalid state"),
+/// A shape of the rules it was read from, in the order they were declared.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Shape(usize);
+
+/// The shapes of a game, which ones beat which, and what everything is worth. Two different
+/// shapes where neither beats the other end in a draw.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rules {
+    names: Vec<String>,
+    points: Vec<i32>,
+    /// Pairs of a winner and the shape it beats.
+    beats: BTreeSet<(Shape, Shape)>,
+    win: i32,
+    draw: i32,
+    lose: i32,
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Self {
+            names: Vec::new(),
+            points: Vec::new(),
+            beats: BTreeSet::new(),
+            win: 6,
+            draw: 3,
+            lose: 0,
         }
     }
 }
 
-impl Tool {
-    fn opposite(&self) -> Self {
-        match self {
-            Tool::Rock => Tool::Paper,
-            Tool::Paper => Tool::Scissors,
-            Tool::Scissors => Tool::Rock,
+fn parse_name<'a>(cursor: &mut Cursor<'a>) -> std::result::Result<&'a str, LineError> {
+    let column = cursor.column();
+    match cursor.take_while(|c| c.is_alphanumeric() || c == '_' || c == '-') {
+        "" => Err(cursor.error_at(column, "expected a name")),
+        name => Ok(name),
+    }
+}
+
+impl Rules {
+    pub fn classic() -> Self {
+        Self::parse(CLASSIC).expect("the classic rules are valid")
+    }
+
+    /// Reads a rule file like `data/day_2.rpsls.rules`, pointing at the line of the file that
+    /// breaks a rule.
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path).map_err(|source| Error::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+
+        Self::parse(&content).map_err(|(line, reason)| Error::Config {
+            path: path.to_path_buf(),
+            line,
+            reason,
+        })
+    }
+
+    /// Reads a rule file, one rule per line. Shapes are declared with their points before being
+    /// used, outcomes default to 6, 3 and 0 points, and `#` starts a comment:
+    ///
+    /// ```text
+    /// shape rock 1
+    /// shape paper 2
+    /// paper beats rock
+    /// win 6
+    /// ```
+    ///
+    /// A shape used before it is declared, a shape beating itself or a rule that does not read
+    /// fails with the 1-based line and the reason, which [`Rules::load`] places in the file.
+    pub fn parse(content: &str) -> std::result::Result<Self, (usize, String)> {
+        let mut rules = Self::default();
+
+        for (index, line) in content.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or_default();
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            parse::line(line, |cursor| rules.parse_rule(cursor))
+                .map_err(|e| (index + 1, e.to_string()))?;
+        }
+
+        if rules.names.is_empty() {
+            return Err((content.lines().count().max(1), "no shapes".to_string()));
         }
+
+        Ok(rules)
     }
 
-    fn find(&self, state: &State) -> Self {
-        match state {
-            State::Draw => self.clone(),
-            State::Win => self.opposite(),
-            State::Lose => self.opposite().opposite(),
+    fn parse_rule(&mut self, cursor: &mut Cursor) -> std::result::Result<(), LineError> {
+        cursor.spaces();
+        let column = cursor.column();
+        let name = parse_name(cursor)?;
+        cursor.spaces();
+
+        match name {
+            "shape" => {
+                let column = cursor.column();
+                let name = parse_name(cursor)?;
+                if self.shape(name).is_some() {
+                    return Err(cursor.error_at(column, "shape declared twice"));
+                }
+                cursor.spaces();
+
+                self.points.push(cursor.int()?);
+                self.names.push(name.to_string());
+            }
+            "win" => self.win = cursor.int()?,
+            "draw" => self.draw = cursor.int()?,
+            "lose" => self.lose = cursor.int()?,
+            name => {
+                let winner = self
+                    .shape(name)
+                    .ok_or_else(|| cursor.error_at(column, "unknown shape"))?;
+                cursor.tag("beats")?;
+                cursor.spaces();
+
+                let losers = cursor.list(",", |cursor| {
+                    cursor.spaces();
+                    let column = cursor.column();
+                    let loser = parse_name(cursor)?;
+                    let loser = self
+                        .shape(loser)
+                        .ok_or_else(|| cursor.error_at(column, "unknown shape"))?;
+
+                    if loser == winner {
+                        Err(cursor.error_at(column, "a shape cannot beat itself"))
+                    } else if self.beats(loser, winner) {
+                        Err(cursor.error_at(column, "shapes cannot beat each other"))
+                    } else {
+                        Ok(loser)
+                    }
+                })?;
+
+                self.beats
+                    .extend(losers.into_iter().map(|loser| (winner, loser)));
+            }
         }
+
+        cursor.spaces();
+        Ok(())
     }
 
-    fn beats(&self, other: &Tool) -> bool {
-        match (self, other) {
-            (Tool::Rock, Tool::Scissors) => true,
-            (Tool::Paper, Tool::Rock) => true,
-            (Tool::Scissors, Tool::Paper) => true,
-            _ => false,
+    pub fn shapes(&self) -> impl Iterator<Item = Shape> {
+        (0..self.names.len()).map(Shape)
+    }
+
+    pub fn shape(&self, name: &str) -> Option<Shape> {
+        self.names.iter().position(|n| n == name).map(Shape)
+    }
+
+    pub fn name(&self, shape: Shape) -> &str {
+        &self.names[shape.0]
+    }
+
+    pub fn beats(&self, winner: Shape, loser: Shape) -> bool {
+        self.beats.contains(&(winner, loser))
+    }
+
+    /// How the game ends for the player of `mine`.
+    pub fn outcome(&self, mine: Shape, theirs: Shape) -> State {
+        if self.beats(mine, theirs) {
+            State::Win
+        } else if self.beats(theirs, mine) {
+            State::Lose
+        } else {
+            State::Draw
         }
     }
 
-    fn points(&self) -> i32 {
-        match self {
-            Tool::Rock => 1,
-            Tool::Paper => 2,
-            Tool::Scissors => 3,
+    /// The points of a state, without the points of the shape.
+    pub fn state_points(&self, state: State) -> i32 {
+        match state {
+            State::Win => self.win,
+            State::Draw => self.draw,
+            State::Lose => self.lose,
         }
     }
-}
 
-impl From<&str> for Tool {
-    fn from(s: &str) -> Self {
-        match s {
-            "X" | "A" => Tool::Rock,
-            "Y" | "B" => Tool::Paper,
-            "Z" | "C" => Tool::Scissors,
-            _ => panic!("Invalid tool"),
+    /// Points for the player of `mine`.
+    pub fn score(&self, mine: Shape, theirs: Shape) -> i32 {
+        self.points[mine.0] + self.state_points(self.outcome(mine, theirs))
+    }
+
+    /// The first shape ending the game in `state` against `theirs`, a draw being `theirs` itself.
+    pub fn response(&self, theirs: Shape, state: State) -> Option<Shape> {
+        if state == State::Draw {
+            return Some(theirs);
         }
+
+        self.shapes()
+            .find(|mine| self.outcome(*mine, theirs) == state)
     }
 }
 
-#[derive(Debug)]
-struct Game {
-    player_a: Tool,
-    secret_b: String,
+/// What the letters of the guide stand for. The first column names the opponent's shape, and
+/// the second one either the shape to play or how the game should end.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Decoder {
+    opponents: BTreeMap<char, Shape>,
+    responses: BTreeMap<char, Shape>,
+    states: BTreeMap<char, State>,
+}
+
+fn parse_letter(cursor: &mut Cursor) -> std::result::Result<char, LineError> {
+    let column = cursor.column();
+    match cursor.char() {
+        Some(letter) if letter.is_alphabetic() => Ok(letter),
+        _ => Err(cursor.error_at(column, "expected a letter")),
+    }
 }
 
-fn get_games(input: &str) -> Vec<Game> {
-    input
-        .lines()
-        .map(|line| {
-            let data = line.split_whitespace().collect::<Vec<&str>>();
-            Game {
-                player_a: data[0].into(),
-                secret_b: data[1].to_string(),
+/// Reads mappings like `X=rock, Y=paper`, with `value` reading what a letter stands for.
+fn parse_mapping<T>(
+    mapping: &str,
+    mut value: impl FnMut(&str) -> Option<T>,
+) -> std::result::Result<BTreeMap<char, T>, LineError> {
+    let pairs = parse::line(mapping, |cursor| {
+        cursor.list(",", |cursor| {
+            cursor.spaces();
+            let letter = parse_letter(cursor)?;
+            cursor.spaces();
+            cursor.tag("=")?;
+            cursor.spaces();
+
+            let column = cursor.column();
+            let name = parse_name(cursor)?;
+            let value = value(name).ok_or_else(|| cursor.error_at(column, "unknown name"))?;
+            cursor.spaces();
+
+            Ok((letter, value))
+        })
+    })?;
+
+    Ok(pairs.into_iter().collect())
+}
+
+impl Decoder {
+    /// The elves' guide: shapes are lettered in order from `A` and from `X`, and `X`, `Y` and
+    /// `Z` also stand for losing, drawing and winning.
+    pub fn lettered(rules: &Rules) -> Self {
+        let letters = |first: u8| (first..=b'Z').map(char::from).zip(rules.shapes()).collect();
+
+        Self {
+            opponents: letters(b'A'),
+            responses: letters(b'X'),
+            states: "XYZ".chars().zip(State::ALL).collect(),
+        }
+    }
+
+    /// Replaces the letters of the opponent's shapes with a mapping like `A=rock, B=paper`.
+    pub fn with_opponents(
+        mut self,
+        rules: &Rules,
+        mapping: &str,
+    ) -> std::result::Result<Self, LineError> {
+        self.opponents = parse_mapping(mapping, |name| rules.shape(name))?;
+        Ok(self)
+    }
+
+    /// Replaces the letters of the shapes to play with a mapping like `X=rock, Y=paper`.
+    pub fn with_responses(
+        mut self,
+        rules: &Rules,
+        mapping: &str,
+    ) -> std::result::Result<Self, LineError> {
+        self.responses = parse_mapping(mapping, |name| rules.shape(name))?;
+        Ok(self)
+    }
+
+    /// Replaces the letters of the end states with a mapping like `X=lose, Y=draw, Z=win`.
+    pub fn with_states(mut self, mapping: &str) -> std::result::Result<Self, LineError> {
+        self.states = parse_mapping(mapping, |name| {
+            State::ALL.into_iter().find(|state| state.name() == name)
+        })?;
+        Ok(self)
+    }
+
+    /// The letters of the shapes to play, like `X=rock, Y=paper, Z=scissors`.
+    pub fn describe_responses(&self, rules: &Rules) -> String {
+        self.responses
+            .iter()
+            .map(|(letter, shape)| format!("{}={}", letter, rules.name(*shape)))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// Every way of giving the letters of the shapes to play to different shapes, in order.
+    pub fn permutations(&self, rules: &Rules) -> Vec<Self> {
+        fn assign(
+            letters: &[char],
+            shapes: &[Shape],
+            current: &mut Vec<(char, Shape)>,
+            found: &mut Vec<BTreeMap<char, Shape>>,
+        ) {
+            let Some((letter, rest)) = letters.split_first() else {
+                found.push(current.iter().copied().collect());
+                return;
+            };
+
+            for shape in shapes {
+                if current.iter().all(|(_, used)| used != shape) {
+                    current.push((*letter, *shape));
+                    assign(rest, shapes, current, found);
+                    current.pop();
+                }
             }
+        }
+
+        let letters = self.responses.keys().copied().collect::<Vec<_>>();
+        let shapes = rules.shapes().collect::<Vec<_>>();
+        let mut found = Vec::new();
+        assign(&letters, &shapes, &mut Vec::new(), &mut found);
+
+        found
+            .into_iter()
+            .map(|responses| Self {
+                responses,
+                ..self.clone()
+            })
+            .collect()
+    }
+}
+
+/// A line of the guide, its letters being read by a [`Decoder`].
+#[derive(Debug, Clone, Copy)]
+pub struct Game {
+    opponent: char,
+    response: char,
+}
+
+fn parse_game(decoder: &Decoder, line: &str) -> std::result::Result<Game, LineError> {
+    let (first_column, first, second_column, second) = match words(line).as_slice() {
+        [(a, first), (b, second)] => (*a, *first, *b, *second),
+        [_] => {
+            return Err(LineError::new(
+                line.len() + 1,
+                line,
+                "missing the second column",
+            ))
+        }
+        [_, _, (c, _), ..] => return Err(LineError::new(*c, line, "unexpected column")),
+        [] => return Err(LineError::new(1, line, "empty line")),
+    };
+
+    let letter = |text: &str, column, known: &dyn Fn(&char) -> bool| {
+        let mut chars = text.chars();
+        match (chars.next(), chars.next()) {
+            (Some(letter), None) if known(&letter) => Ok(letter),
+            _ => Err(LineError::new(
+                column,
+                line,
+                format!("invalid tool `{}`", text),
+            )),
+        }
+    };
+
+    Ok(Game {
+        opponent: letter(first, first_column, &|c| decoder.opponents.contains_key(c))?,
+        response: letter(second, second_column, &|c| {
+            decoder.responses.contains_key(c) || decoder.states.contains_key(c)
+        })?,
+    })
+}
+
+/// Reads the guide, every letter having to mean something to `decoder`.
+pub fn get_games(decoder: &Decoder, input: &str) -> Result<Vec<Game>> {
+    parse::each_line(DAY, input, |line| parse_game(decoder, line))
+}
+
+fn decode<T: Copy>(letters: &BTreeMap<char, T>, letter: char, part: u8) -> Result<T> {
+    letters.get(&letter).copied().ok_or_else(|| {
+        let reason = format!("`{}` does not stand for anything", letter);
+        Error::no_solution(DAY, part, reason)
+    })
+}
+
+/// What each player scored over the whole guide.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Totals {
+    pub player_a: i32,
+    pub player_b: i32,
+}
+
+/// Score of both players, reading the second column as the shape player B plays.
+pub fn score_shapes(rules: &Rules, decoder: &Decoder, games: &[Game]) -> Result<Totals> {
+    games.iter().try_fold(Totals::default(), |totals, game| {
+        let theirs = decode(&decoder.opponents, game.opponent, 1)?;
+        let mine = decode(&decoder.responses, game.response, 1)?;
+        Ok(Totals {
+            player_a: totals.player_a + rules.score(theirs, mine),
+            player_b: totals.player_b + rules.score(mine, theirs),
         })
-        .collect::<Vec<_>>()
+    })
 }
 
-pub fn run(input: String) {
-    let games = get_games(&input);
+/// Score of player B, reading the second column as the end state of the game.
+pub fn score_states(rules: &Rules, decoder: &Decoder, games: &[Game]) -> Result<i32> {
+    games.iter().try_fold(0, |score, game| {
+        let theirs = decode(&decoder.opponents, game.opponent, 2)?;
+        let state = decode(&decoder.states, game.response, 2)?;
 
-    let mut a = 0;
-    let mut b = 0;
-    for game in &games {
-        let player_b: Tool = game.secret_b.as_str().into();
-        a += game.player_a.points();
-        b += player_b.points();
+        let mine = rules.response(theirs, state).ok_or_else(|| {
+            let reason = format!(
+                "no shape can {} against {}",
+                state.name(),
+                rules.name(theirs)
+            );
+            Error::no_solution(DAY, 2, reason)
+        })?;
 
-        if game.player_a == player_b {
-            a += DRAW;
-            b += DRAW;
-        } else {
-            if game.player_a.beats(&player_b) {
-                a += WIN;
-            } else {
-                b += WIN;
+        Ok(score + rules.score(mine, theirs))
+    })
+}
+
+/// The score of reading the second column as shapes under every permutation of its letters,
+/// each described like `X=rock, Y=paper, Z=scissors`.
+pub fn score_permutations(
+    rules: &Rules,
+    decoder: &Decoder,
+    games: &[Game],
+) -> Result<Vec<(String, i32)>> {
+    decoder
+        .permutations(rules)
+        .iter()
+        .map(|permutation| {
+            let score = score_shapes(rules, permutation, games)?.player_b;
+            Ok((permutation.describe_responses(rules), score))
+        })
+        .collect()
+}
+
+/// The guide along with the rules it is played with and how its letters are read.
+pub struct Guide {
+    rules: Rules,
+    decoder: Decoder,
+    games: Vec<Game>,
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Input = Guide;
+    /// The score of player B, who follows the guide.
+    type PartOne = i32;
+    type PartTwo = i32;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let rules = Rules::classic();
+        let decoder = Decoder::lettered(&rules);
+        let games = get_games(&decoder, input)?;
+
+        Ok(Guide {
+            rules,
+            decoder,
+            games,
+        })
+    }
+
+    fn part_one(guide: &Self::Input) -> Result<Self::PartOne> {
+        score_shapes(&guide.rules, &guide.decoder, &guide.games).map(|totals| totals.player_b)
+    }
+
+    fn part_two(guide: &Self::Input) -> Result<Self::PartTwo> {
+        score_states(&guide.rules, &guide.decoder, &guide.games)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{input, solution::solve};
+
+    const EXAMPLE: &str = include_str!("../../data/day_2.example.txt");
+    const RPSLS: &str = include_str!("../../data/day_2.rpsls.rules");
+
+    #[test]
+    fn tools_beat_each_other_in_a_cycle() {
+        let rules = Rules::classic();
+        let shape = |name| rules.shape(name).unwrap();
+        let (rock, paper, scissors) = (shape("rock"), shape("paper"), shape("scissors"));
+
+        assert!(rules.beats(rock, scissors));
+        assert!(rules.beats(paper, rock));
+        assert!(rules.beats(scissors, paper));
+        assert!(!rules.beats(rock, paper));
+        assert!(!rules.beats(rock, rock));
+    }
+
+    #[test]
+    fn finds_the_tool_for_an_end_state() {
+        let rules = Rules::classic();
+        let shape = |name| rules.shape(name).unwrap();
+        let (rock, paper, scissors) = (shape("rock"), shape("paper"), shape("scissors"));
+
+        assert_eq!(rules.response(rock, State::Draw), Some(rock));
+        assert_eq!(rules.response(paper, State::Lose), Some(rock));
+        assert_eq!(rules.response(scissors, State::Win), Some(rock));
+    }
+
+    #[test]
+    fn plays_rock_paper_scissors_lizard_spock() {
+        let rules = Rules::parse(RPSLS).unwrap();
+        assert_eq!(rules.shapes().count(), 5);
+
+        // Every two different shapes have a winner
+        for a in rules.shapes() {
+            for b in rules.shapes().filter(|b| *b != a) {
+                assert_ne!(rules.outcome(a, b), State::Draw);
+                assert_eq!(rules.beats(a, b), !rules.beats(b, a));
             }
         }
+
+        let shape = |name| rules.shape(name).unwrap();
+        assert_eq!(rules.score(shape("spock"), shape("rock")), 11);
+        assert_eq!(rules.score(shape("lizard"), shape("scissors")), 4);
+        assert_eq!(
+            rules.response(shape("rock"), State::Win),
+            Some(shape("paper"))
+        );
+
+        let decoder = Decoder::lettered(&rules);
+        let games = get_games(&decoder, "E X\nD Z").unwrap();
+        assert_eq!(
+            score_shapes(&rules, &decoder, &games).unwrap().player_b,
+            1 + 3 + 6
+        );
+        // Rock is the first shape losing to Spock, and the first one beating the lizard
+        assert_eq!(score_states(&rules, &decoder, &games).unwrap(), 1 + 1 + 6);
     }
 
-    println!("Day 2:");
-    println!("  Part 1:");
-    println!("    Player A: {}", a);
-    println!("    Player B: {}", b);
+    #[test]
+    fn scores_states_and_shapes_as_configured() {
+        let rules = Rules::parse(
+            "shape a 10\nshape b 20\nshape c 30  # no one beats c\nb beats a\nwin 100\ndraw -1",
+        )
+        .unwrap();
+        let shape = |name| rules.shape(name).unwrap();
 
-    // Part 2
-    let mut score = 0;
+        assert_eq!(rules.score(shape("b"), shape("a")), 120);
+        assert_eq!(rules.score(shape("a"), shape("b")), 10);
+        assert_eq!(rules.score(shape("c"), shape("a")), 29);
+        assert_eq!(rules.response(shape("c"), State::Win), None);
 
-    for game in games {
-        let end_state: State = game.secret_b.as_str().into();
-        let tool = game.player_a.find(&end_state);
+        let decoder = Decoder::lettered(&rules);
+        let games = get_games(&decoder, "C Z").unwrap();
+        assert!(score_states(&rules, &decoder, &games).is_err());
+    }
 
-        score += tool.points();
-        score += end_state.points();
+    #[test]
+    fn rejects_invalid_rules() {
+        let error = |content| Rules::parse(content).unwrap_err();
+        assert_eq!(error("shape rock 1\nrock beats paper").0, 2);
+        assert_eq!(
+            error("shape rock 1\nrock beats rock").1,
+            "column 12: a shape cannot beat itself in `rock beats rock`"
+        );
+        assert_eq!(
+            error("shape a 1\nshape b 2\na beats b\nb beats a").1,
+            "column 9: shapes cannot beat each other in `b beats a`"
+        );
+        assert_eq!(error("shape a 1\nshape a 2").0, 2);
+        assert_eq!(
+            error("shape a x").1,
+            "column 9: expected a number in `shape a x`"
+        );
+        assert_eq!(error("# nothing\n").0, 1);
     }
 
-    println!("  Part 2:");
-    println!("    Score: {}", score);
+    #[test]
+    fn loads_rule_files() {
+        let rules = Rules::load(&input::data_dir().join("day_2.rpsls.rules")).unwrap();
+        assert_eq!(rules, Rules::parse(RPSLS).unwrap());
+
+        let missing = Rules::load(&input::data_dir().join("day_2.missing.rules"));
+        assert!(matches!(missing, Err(Error::Io { .. })));
+    }
+
+    #[test]
+    fn decodes_letters_from_mappings() {
+        let rules = Rules::classic();
+        let decoder = Decoder::lettered(&rules)
+            .with_opponents(&rules, "R=rock, P=paper, S=scissors")
+            .unwrap()
+            .with_responses(&rules, "X=scissors, Y=rock")
+            .unwrap()
+            .with_states("Y=win, X=lose")
+            .unwrap();
+        let games = get_games(&decoder, "R X\nS Y").unwrap();
+
+        // Scissors losing to rock, then rock beating scissors, whichever way the column is read
+        assert_eq!(
+            score_shapes(&rules, &decoder, &games).unwrap().player_b,
+            3 + 7
+        );
+        assert_eq!(score_states(&rules, &decoder, &games).unwrap(), 3 + 7);
+        assert!(get_games(&decoder, "A X").is_err());
+        assert!(get_games(&decoder, "R Z").is_err());
+
+        let error = Decoder::lettered(&rules).with_responses(&rules, "X=rock, Y=stone");
+        assert_eq!(error.unwrap_err().column, 11);
+        assert!(Decoder::lettered(&rules).with_states("X=tie").is_err());
+    }
+
+    #[test]
+    fn scores_every_permutation_of_the_responses() {
+        let rules = Rules::classic();
+        let decoder = Decoder::lettered(&rules);
+        let games = get_games(&decoder, EXAMPLE).unwrap();
+
+        let scores = score_permutations(&rules, &decoder, &games).unwrap();
+        let expected = [
+            ("X=rock, Y=paper, Z=scissors", 15),
+            ("X=rock, Y=scissors, Z=paper", 6),
+            ("X=paper, Y=rock, Z=scissors", 15),
+            ("X=paper, Y=scissors, Z=rock", 15),
+            ("X=scissors, Y=rock, Z=paper", 15),
+            ("X=scissors, Y=paper, Z=rock", 24),
+        ];
+        assert_eq!(
+            scores,
+            expected.map(|(mapping, score)| (mapping.to_string(), score))
+        );
+    }
+
+    #[test]
+    fn reports_invalid_tools() {
+        let decoder = Decoder::lettered(&Rules::classic());
+        let error = get_games(&decoder, "A Y\nB Q").unwrap_err();
+        assert!(matches!(
+            error,
+            Error::Parse {
+                day: 2,
+                line: 2,
+                column: 3,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn solves_example() {
+        let solved = solve::<Day2>(EXAMPLE).unwrap();
+        assert_eq!(solved.part_one, 15.into());
+        assert_eq!(solved.part_two, 12.into());
+    }
+
+    #[test]
+    fn scores_both_players() {
+        let guide = Day2::parse(EXAMPLE).unwrap();
+        assert_eq!(
+            score_shapes(&guide.rules, &guide.decoder, &guide.games).unwrap(),
+            Totals {
+                player_a: 1 + 8 + 6,
+                player_b: 8 + 1 + 6,
+            }
+        );
+    }
 }
@@ -1,30 +1,47 @@
+use crate::{
+    error::{LineError, Result},
+    parse,
+    solution::Solution,
+};
 use std::collections::HashSet;
 
+const DAY: u8 = 3;
 const ABC: &str = "abcdefghijklmnopqrstuvwxyz";
 
 #[derive(Clone)]
-struct Rucksack {
+pub struct Rucksack {
     first: String,
     second: String,
 }
 
 impl Rucksack {
-    fn total(&self) -> String {
+    pub fn total(&self) -> String {
         self.first.clone() + &self.second
     }
 }
 
-fn prepare_rucksacks(input: &str) -> Vec<Rucksack> {
-    input
-        .lines()
-        .map(|line| {
-            let half = line.len() / 2;
-            Rucksack {
-                first: line[..half].to_string(),
-                second: line[half..].to_string(),
-            }
-        })
-        .collect()
+fn parse_rucksack(line: &str) -> std::result::Result<Rucksack, LineError> {
+    if let Some(index) = line.find(|c: char| !c.is_ascii_alphabetic()) {
+        return Err(LineError::new(index + 1, line, "item is not a letter"));
+    }
+
+    if line.len() % 2 != 0 {
+        return Err(LineError::new(
+            line.len(),
+            line,
+            "compartments are not the same size",
+        ));
+    }
+
+    let half = line.len() / 2;
+    Ok(Rucksack {
+        first: line[..half].to_string(),
+        second: line[half..].to_string(),
+    })
+}
+
+pub fn prepare_rucksacks(input: &str) -> Result<Vec<Rucksack>> {
+    parse::each_line(DAY, input, parse_rucksack)
 }
 
 fn get_letter_position(letter: &char) -> usize {
@@ -32,7 +49,7 @@ fn get_letter_position(letter: &char) -> usize {
     ABC.chars().position(|c| c == letter).unwrap()
 }
 
-fn get_weight(letter: char) -> usize {
+pub fn get_weight(letter: char) -> usize {
     let s = get_letter_position(&letter) + 1;
     if letter.is_uppercase() {
         s + ABC.len()
@@ -41,52 +58,95 @@ fn get_weight(letter: char) -> usize {
     }
 }
 
-pub fn run(input: String) {
-    let rucksacks = prepare_rucksacks(&input);
+pub struct Day3;
 
-    let repeated_letters = rucksacks
-        .iter()
-        .filter_map(|r| {
-            let set = r.first.chars().collect::<HashSet<_>>();
+impl Solution for Day3 {
+    type Input = Vec<Rucksack>;
+    type PartOne = usize;
+    type PartTwo = usize;
 
-            let repeated = r
-                .second
-                .chars()
-                .filter(|c| set.contains(c))
-                .collect::<Vec<_>>();
+    fn parse(input: &str) -> Result<Self::Input> {
+        prepare_rucksacks(input)
+    }
 
-            repeated.first().copied()
-        })
-        .collect::<Vec<_>>();
+    fn part_one(rucksacks: &Self::Input) -> Result<Self::PartOne> {
+        let repeated_letters = rucksacks
+            .iter()
+            .filter_map(|r| {
+                let set = r.first.chars().collect::<HashSet<_>>();
 
-    let weight = repeated_letters
-        .iter()
-        .map(|c| get_weight(*c))
-        .sum::<usize>();
+                let repeated = r
+                    .second
+                    .chars()
+                    .filter(|c| set.contains(c))
+                    .collect::<Vec<_>>();
 
-    println!("Day 3:");
-    println!("  Part 1: {}", weight);
+                repeated.first().copied()
+            })
+            .collect::<Vec<_>>();
 
-    // Part 2
+        Ok(repeated_letters
+            .iter()
+            .map(|c| get_weight(*c))
+            .sum::<usize>())
+    }
 
-    let groups = rucksacks.chunks(3);
-    let mut total = 0;
-    for group in groups {
-        let first = group[0].total().chars().collect::<HashSet<_>>();
+    fn part_two(rucksacks: &Self::Input) -> Result<Self::PartTwo> {
+        let groups = rucksacks.chunks(3);
+        let mut total = 0;
+        for group in groups {
+            let first = group[0].total().chars().collect::<HashSet<_>>();
+
+            let repeated = &group[1..]
+                .iter()
+                .fold(first, |acc, x| {
+                    acc.intersection(&x.total().chars().collect::<HashSet<_>>())
+                        .copied()
+                        .collect::<HashSet<_>>()
+                })
+                .into_iter()
+                .map(get_weight)
+                .sum::<usize>();
+
+            total += repeated;
+        }
+
+        Ok(total)
+    }
+}
 
-        let repeated = &group[1..]
-            .iter()
-            .fold(first, |acc, x| {
-                acc.intersection(&x.total().chars().collect::<HashSet<_>>())
-                    .copied()
-                    .collect::<HashSet<_>>()
-            })
-            .into_iter()
-            .map(|c| get_weight(c))
-            .sum::<usize>();
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::solve;
+
+    const EXAMPLE: &str = include_str!("../../data/day_3.example.txt");
 
-        total += repeated;
+    #[test]
+    fn weights_letters() {
+        assert_eq!(get_weight('a'), 1);
+        assert_eq!(get_weight('z'), 26);
+        assert_eq!(get_weight('A'), 27);
+        assert_eq!(get_weight('Z'), 52);
     }
 
-    println!("  Part 2: {}", total);
+    #[test]
+    fn splits_rucksacks_in_half() {
+        let rucksacks = prepare_rucksacks("abCD").unwrap();
+        assert_eq!(rucksacks[0].first, "ab");
+        assert_eq!(rucksacks[0].second, "CD");
+    }
+
+    #[test]
+    fn rejects_odd_rucksacks() {
+        assert!(prepare_rucksacks("abc").is_err());
+        assert!(prepare_rucksacks("a1").is_err());
+    }
+
+    #[test]
+    fn solves_example() {
+        let solved = solve::<Day3>(EXAMPLE).unwrap();
+        assert_eq!(solved.part_one, 157.into());
+        assert_eq!(solved.part_two, 70.into());
+    }
 }
@@ -1,59 +1,127 @@
 use std::ops::RangeInclusive;
 
+use crate::{
+    error::{LineError, Result},
+    parse::{self, Cursor},
+    solution::Solution,
+};
+
+const DAY: u8 = 4;
+
 #[derive(Debug)]
-struct Pair {
+pub struct Pair {
     range_a: RangeInclusive<i32>,
     range_b: RangeInclusive<i32>,
 }
 
 impl Pair {
-    fn intersect(&self) -> bool {
+    pub fn intersect(&self) -> bool {
         self.range_b.start() >= self.range_a.start() && self.range_b.end() <= self.range_a.end()
             || self.range_a.start() >= self.range_b.start()
                 && self.range_a.end() <= self.range_b.end()
     }
 
-    fn overlap(&self) -> bool {
+    pub fn overlap(&self) -> bool {
         self.range_a.start() <= self.range_b.start() && self.range_a.end() >= self.range_b.start()
             || self.range_b.start() <= self.range_a.start()
                 && self.range_b.end() >= self.range_a.start()
     }
 }
 
-fn parse_range(pair: &str) -> RangeInclusive<i32> {
-    let mut parts = pair.split('-');
+/// Reads `start-end`.
+pub fn parse_range(cursor: &mut Cursor) -> std::result::Result<RangeInclusive<i32>, LineError> {
+    let start = cursor.int()?;
+    cursor.tag("-")?;
+    let end = cursor.int()?;
 
-    let start = parts.next().unwrap();
-    let end = parts.next().unwrap();
+    Ok(start..=end)
+}
 
-    let start = start.parse::<i32>().unwrap();
-    let end = end.parse::<i32>().unwrap();
+fn parse_pair(line: &str) -> std::result::Result<Pair, LineError> {
+    parse::line(line, |cursor| {
+        let range_a = parse_range(cursor)?;
+        cursor.tag(",")?;
+        let range_b = parse_range(cursor)?;
 
-    start..=end
+        Ok(Pair { range_a, range_b })
+    })
 }
 
-fn parse_pairs(input: &str) -> Vec<Pair> {
-    input
-        .lines()
-        .map(|line| {
-            let mut parts = line.split(",");
-            Pair {
-                range_a: parse_range(parts.next().unwrap()),
-                range_b: parse_range(parts.next().unwrap()),
-            }
-        })
-        .collect()
+pub fn parse_pairs(input: &str) -> Result<Vec<Pair>> {
+    parse::each_line(DAY, input, parse_pair)
 }
 
-pub fn run(input: String) {
-    let pairs = parse_pairs(&input);
+pub struct Day4;
 
-    let intersect = pairs.iter().filter(|pair| pair.intersect()).count();
+impl Solution for Day4 {
+    type Input = Vec<Pair>;
+    type PartOne = usize;
+    type PartTwo = usize;
 
-    println!("Day 4:");
-    println!("  {}", intersect);
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse_pairs(input)
+    }
 
-    let overlap = pairs.iter().filter(|pair| pair.overlap()).count();
+    fn part_one(pairs: &Self::Input) -> Result<Self::PartOne> {
+        Ok(pairs.iter().filter(|pair| pair.intersect()).count())
+    }
 
-    println!("  {}", overlap);
+    fn part_two(pairs: &Self::Input) -> Result<Self::PartTwo> {
+        Ok(pairs.iter().filter(|pair| pair.overlap()).count())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::solve;
+
+    const EXAMPLE: &str = include_str!("../../data/day_4.example.txt");
+
+    fn pair(a: RangeInclusive<i32>, b: RangeInclusive<i32>) -> Pair {
+        Pair {
+            range_a: a,
+            range_b: b,
+        }
+    }
+
+    #[test]
+    fn parses_ranges() {
+        let range = |text| parse::line(text, parse_range);
+        assert_eq!(range("2-4"), Ok(2..=4));
+        assert_eq!(range("6-6"), Ok(6..=6));
+        assert_eq!(range("2-x").unwrap_err().column, 3);
+        assert_eq!(range("24").unwrap_err().column, 3);
+    }
+
+    #[test]
+    fn detects_contained_ranges() {
+        assert!(pair(2..=8, 3..=7).intersect());
+        assert!(pair(4..=6, 6..=6).intersect());
+        assert!(!pair(2..=4, 6..=8).intersect());
+    }
+
+    #[test]
+    fn detects_overlapping_ranges() {
+        assert!(pair(5..=7, 7..=9).overlap());
+        assert!(pair(7..=9, 5..=7).overlap());
+        assert!(pair(2..=6, 4..=8).overlap());
+        assert!(!pair(2..=3, 4..=5).overlap());
+    }
+
+    #[test]
+    fn reports_the_column_of_the_second_range() {
+        let error = parse_pairs("2-4,6-x").unwrap_err();
+        assert!(matches!(
+            error,
+            crate::error::Error::Parse { column: 7, .. }
+        ));
+    }
+
+    #[test]
+    fn solves_example() {
+        let solved = solve::<Day4>(EXAMPLE).unwrap();
+        assert_eq!(solved.part_one, 2.into());
+        assert_eq!(solved.part_two, 4.into());
+    }
 }
@@ -1,101 +1,123 @@
+use crate::{
+    error::{LineError, Result},
+    parse::{self, Block, Cursor},
+    solution::Solution,
+};
+
+const DAY: u8 = 5;
+
+/// Moves `amount` crates between two stacks, numbered from 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Move {
+    amount: usize,
+    from: usize,
+    to: usize,
+}
+
 #[derive(Debug, Clone)]
-struct Boat {
+pub struct Boat {
     lines: Vec<Vec<char>>,
-    instructions: Vec<Vec<u32>>,
+    instructions: Vec<Move>,
 }
 
-fn parse(input: &str) -> Boat {
-    let lines = input.lines();
-
-    let mut index = 0;
-    let mut boat = Boat {
-        lines: Vec::new(),
-        instructions: Vec::new(),
+/// Reads the stacks bottom up from the drawing, whose last line labels them.
+fn parse_drawing(drawing: &Block) -> Result<Vec<Vec<char>>> {
+    let Some((labels, crates)) = drawing.lines.split_last() else {
+        return Ok(Vec::new());
     };
 
-    let mut instructions = false;
-
-    let mut instruction_buffer = Vec::new();
-    for line in lines {
-        let chars = line.chars();
-        let is_division = chars.count() == 0;
-
-        if is_division {
-            instructions = true;
-            continue;
-        }
+    let mut stacks = vec![Vec::new(); parse::words(labels).len()];
 
-        if instructions {
-            let mut buffer = String::new();
-            for char in line.chars() {
-                if char.is_numeric() {
-                    buffer.push(char);
-                } else if buffer.len() > 0 {
-                    instruction_buffer.push(buffer.parse::<u32>().unwrap());
-                    buffer = String::new();
-                }
+    for (line, text) in drawing.numbered().take(crates.len()) {
+        // Crates are drawn as `[X]` every 4 columns
+        for (index, item) in text.chars().skip(1).step_by(4).enumerate() {
+            if item == ' ' {
+                continue;
             }
 
-            if buffer.len() > 0 {
-                instruction_buffer.push(buffer.parse().unwrap());
+            let error = |reason| LineError::new(index * 4 + 2, text, reason).at(DAY, line);
+            if !item.is_alphabetic() {
+                return Err(error("expected a crate like `[A]`"));
             }
 
-            boat.instructions.push(instruction_buffer.clone());
-            instruction_buffer.clear();
-        } else {
-            let mut cursor = 0;
-            for char in line.chars() {
-                if index == boat.lines.len() {
-                    boat.lines.insert(index, Vec::new());
-                }
-
-                if char.is_alphabetic() {
-                    if let Some(line) = boat.lines.get_mut(index) {
-                        line.insert(0, char);
-                    }
-                }
-
-                cursor += 1;
-
-                if cursor == 4 {
-                    index += 1;
-                    cursor = 0;
-                }
-            }
-
-            index = 0;
+            stacks
+                .get_mut(index)
+                .ok_or_else(|| error("crate outside of the labelled stacks"))?
+                .insert(0, item);
         }
     }
 
-    boat
+    Ok(stacks)
 }
 
-fn arrange(boat: &mut Boat, mantain_order: bool) {
-    for x in &boat.instructions {
-        let quantity = x[0];
-        let from = x[1] - 1;
-        let to = x[2] - 1;
-
-        if let Some(from) = boat.lines.get_mut(from as usize) {
-            let mut buffer = Vec::with_capacity(quantity as usize);
-            for _ in 0..quantity {
-                if let Some(item) = from.pop() {
-                    if mantain_order {
-                        buffer.insert(0, item);
-                    } else {
-                        buffer.push(item);
-                    }
-                }
-            }
+fn parse_stack(cursor: &mut Cursor, stacks: usize) -> std::result::Result<usize, LineError> {
+    let column = cursor.column();
+    let stack = cursor.uint()?;
 
-            if let Some(to) = boat.lines.get_mut(to as usize) {
-                to.extend(buffer);
-            }
+    if (1..=stacks).contains(&stack) {
+        Ok(stack)
+    } else {
+        Err(cursor.error_at(column, "unknown stack"))
+    }
+}
+
+fn parse_move(line: &str, stacks: usize) -> std::result::Result<Move, LineError> {
+    parse::line(line, |cursor| {
+        cursor.tag("move ")?;
+        let amount = cursor.uint()?;
+        cursor.tag(" from ")?;
+        let from = parse_stack(cursor, stacks)?;
+        cursor.tag(" to ")?;
+        let to = parse_stack(cursor, stacks)?;
+
+        Ok(Move { amount, from, to })
+    })
+}
+
+pub fn parse(input: &str) -> Result<Boat> {
+    let mut blocks = parse::blocks(input);
+
+    let lines = match blocks.next() {
+        Some(drawing) => parse_drawing(&drawing)?,
+        None => Vec::new(),
+    };
+
+    let instructions = match blocks.next() {
+        Some(procedure) => procedure
+            .numbered()
+            .map(|(line, text)| parse_move(text, lines.len()).map_err(|e| e.at(DAY, line)))
+            .collect::<Result<_>>()?,
+        None => Vec::new(),
+    };
+
+    if let Some(extra) = blocks.next() {
+        let error = LineError::new(
+            1,
+            extra.lines[0],
+            "expected the drawing and the procedure only",
+        );
+        return Err(error.at(DAY, extra.first_line));
+    }
+
+    Ok(Boat {
+        lines,
+        instructions,
+    })
+}
+
+pub fn arrange(boat: &mut Boat, mantain_order: bool) {
+    for step in &boat.instructions {
+        let from = &mut boat.lines[step.from - 1];
+        let mut buffer = from.split_off(from.len().saturating_sub(step.amount));
+        if !mantain_order {
+            buffer.reverse();
         }
+
+        boat.lines[step.to - 1].extend(buffer);
     }
 }
 
-fn get_heads(boat: &Boat) -> String {
+pub fn get_heads(boat: &Boat) -> String {
     // print heads
     let mut heads = String::new();
     for line in &boat.lines {
@@ -107,14 +129,95 @@ fn get_heads(boat: &Boat) -> String {
     heads
 }
 
-pub fn run(input: String) {
-    let mut boat = parse(&input);
-    let mut boat2 = boat.clone();
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Input = Boat;
+    type PartOne = String;
+    type PartTwo = String;
+
+    // The drawing of the stacks is padded with spaces
+    const KEEPS_TRAILING_WHITESPACE: bool = true;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse(input)
+    }
+
+    fn part_one(boat: &Self::Input) -> Result<Self::PartOne> {
+        let mut boat = boat.clone();
+        arrange(&mut boat, false);
+        Ok(get_heads(&boat))
+    }
+
+    fn part_two(boat: &Self::Input) -> Result<Self::PartTwo> {
+        let mut boat = boat.clone();
+        arrange(&mut boat, true);
+        Ok(get_heads(&boat))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::solve;
+
+    const EXAMPLE: &str = include_str!("../../data/day_5.example.txt");
+
+    #[test]
+    fn parses_stacks_and_instructions() {
+        let boat = parse(EXAMPLE).unwrap();
+
+        assert_eq!(
+            boat.lines,
+            vec![vec!['Z', 'N'], vec!['M', 'C', 'D'], vec!['P']]
+        );
+        assert_eq!(
+            boat.instructions[0],
+            Move {
+                amount: 1,
+                from: 2,
+                to: 1
+            }
+        );
+        assert_eq!(boat.instructions.len(), 4);
+    }
 
-    arrange(&mut boat, false);
-    arrange(&mut boat2, true);
+    #[test]
+    fn rejects_unknown_stacks() {
+        let input = "[A]\n 1 \n\nmove 1 from 1 to 2\n";
+        assert!(parse(input).is_err());
+    }
 
-    println!("Day 5:");
-    println!("  Part 1: {}", get_heads(&boat));
-    println!("  Part 2: {}", get_heads(&boat2));
+    #[test]
+    fn reports_where_instructions_fail() {
+        let error = parse("[A] [B]\n 1   2 \n\nmove 1 from 1 to 2\nmove 1 from x to 2\n");
+        assert_eq!(
+            error.unwrap_err().to_string(),
+            "day 5, line 5, column 13: expected a number in `move 1 from x to 2`"
+        );
+
+        let error = parse("[A] [B] [C]\n 1   2 \n").unwrap_err();
+        assert!(matches!(
+            error,
+            crate::error::Error::Parse {
+                line: 1,
+                column: 10,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn arranges_one_crate_at_a_time() {
+        let mut boat = parse(EXAMPLE).unwrap();
+        arrange(&mut boat, false);
+        assert_eq!(get_heads(&boat), "CMZ");
+    }
+
+    #[test]
+    fn solves_example() {
+        let solved = solve::<Day5>(EXAMPLE).unwrap();
+        assert_eq!(solved.part_one, "CMZ".to_string().into());
+        assert_eq!(solved.part_two, "MCD".to_string().into());
+    }
 }
@@ -1,6 +1,13 @@
 use std::collections::HashSet;
 
-fn find_marker(input: &str, size: usize) -> u32 {
+use crate::{
+    error::{Error, LineError, Result},
+    solution::Solution,
+};
+
+const DAY: u8 = 6;
+
+pub fn find_marker(input: &str, size: usize) -> Option<u32> {
     for index in 0..input.chars().count() {
         if index >= size {
             let mut buff = HashSet::new();
@@ -9,19 +16,72 @@ fn find_marker(input: &str, size: usize) -> u32 {
             buff.extend(sub.chars());
 
             if buff.len() == size {
-                return index as u32;
+                return Some(index as u32);
             }
         }
     }
 
-    panic!("No marker found");
+    None
+}
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    type Input = String;
+    type PartOne = u32;
+    type PartTwo = u32;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let input = input.trim_end();
+
+        if let Some(index) = input.find(|c: char| !c.is_ascii_lowercase()) {
+            let line = input[..index].matches('\n').count() + 1;
+            let column = index - input[..index].rfind('\n').map_or(0, |i| i + 1) + 1;
+            let text = input.lines().nth(line - 1).unwrap_or_default();
+
+            return Err(LineError::new(column, text, "unexpected character").at(DAY, line));
+        }
+
+        Ok(input.to_string())
+    }
+
+    fn part_one(input: &Self::Input) -> Result<Self::PartOne> {
+        find_marker(input, 4).ok_or_else(|| Error::no_solution(DAY, 1, "no marker found"))
+    }
+
+    fn part_two(input: &Self::Input) -> Result<Self::PartTwo> {
+        find_marker(input, 14).ok_or_else(|| Error::no_solution(DAY, 2, "no marker found"))
+    }
 }
 
-pub fn run(input: String) {
-    let marker = find_marker(&input, 4);
-    let message = find_marker(&input, 14);
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_markers() {
+        let examples = [
+            ("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 7, 19),
+            ("bvwbjplbgvbhsrlpgdmjqwftvncz", 5, 23),
+            ("nppdvjthqldpwncqszvftbrmjlhg", 6, 23),
+            ("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 10, 29),
+            ("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 11, 26),
+        ];
 
-    println!("Day 6");
-    println!("  Part 1: {}", marker);
-    println!("  Part 2: {}", message);
+        for (input, packet, message) in examples {
+            assert_eq!(find_marker(input, 4), Some(packet), "{}", input);
+            assert_eq!(find_marker(input, 14), Some(message), "{}", input);
+        }
+    }
+
+    #[test]
+    fn reports_missing_markers() {
+        let input = Day6::parse("aaaa").unwrap();
+        assert!(Day6::part_one(&input).is_err());
+    }
+
+    #[test]
+    fn rejects_unexpected_characters() {
+        assert!(Day6::parse("abc1").is_err());
+    }
 }
@@ -1,54 +1,138 @@
 use std::collections::HashMap;
 
+use crate::{
+    error::{Error, LineError, Result},
+    parse,
+    solution::Solution,
+};
+
+const DAY: u8 = 7;
 const TOTAL_SPACE: u64 = 70000000;
 const REQUIRED_SPACE: u64 = 30000000;
 
-fn find_candidate_to_free_space(folders: &HashMap<String, u64>) -> u64 {
-    let total_used = folders.get("/").unwrap();
-    let space_left = TOTAL_SPACE - total_used;
+pub fn find_candidate_to_free_space(folders: &HashMap<String, u64>) -> Option<u64> {
+    let total_used = folders.get("/")?;
+    let space_left = TOTAL_SPACE.checked_sub(*total_used)?;
 
-    *folders
+    folders
         .values()
         .filter(|v| space_left + **v >= REQUIRED_SPACE)
         .min()
-        .unwrap()
+        .copied()
+}
+
+/// A line of the terminal output.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Entry<'a> {
+    Cd(&'a str),
+    Ls,
+    Dir(&'a str),
+    File(u64),
+}
+
+pub fn parse_entry(line: &str) -> std::result::Result<Entry<'_>, LineError> {
+    parse::line(line, |cursor| {
+        if cursor.eat("$ ") {
+            let column = cursor.column();
+            return match cursor.word()? {
+                "cd" => {
+                    cursor.tag(" ")?;
+                    cursor.word().map(Entry::Cd)
+                }
+                "ls" => Ok(Entry::Ls),
+                _ => Err(cursor.error_at(column, "unknown command")),
+            };
+        }
+
+        if cursor.eat("dir ") {
+            return cursor.word().map(Entry::Dir);
+        }
+
+        let size = cursor.uint()?;
+        cursor.tag(" ")?;
+        cursor.word()?;
+
+        Ok(Entry::File(size))
+    })
 }
 
-pub fn run(input: String) {
+pub fn parse_folders(input: &str) -> Result<HashMap<String, u64>> {
     let mut route = Vec::new();
 
     let mut folders = HashMap::new();
 
-    for line in input.lines() {
-        let split = line.split_whitespace().collect::<Vec<&str>>();
-
-        match split.as_slice() {
-            ["$", "cd", to] => {
-                let is_back = to == &"..";
-                if is_back {
-                    route.pop();
-                } else {
-                    route.push(to.trim().to_string());
-                }
+    for entry in parse::each_line(DAY, input, parse_entry)? {
+        match entry {
+            Entry::Cd("..") => {
+                route.pop();
             }
-            [size, _] if size.chars().all(|x| x.is_numeric()) => {
-                let size = size.parse::<u64>().unwrap();
-
+            Entry::Cd(to) => route.push(to),
+            Entry::Ls | Entry::Dir(_) => {}
+            Entry::File(size) => {
                 for index in 0..route.len() {
                     let path = route[0..=index].join("/");
                     let entry = folders.entry(path).or_insert(0);
                     *entry += size;
                 }
             }
-            _ => {}
         }
     }
 
-    let sum = folders.values().filter(|v| v <= &&100000).sum::<u64>();
+    Ok(folders)
+}
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    type Input = HashMap<String, u64>;
+    type PartOne = u64;
+    type PartTwo = u64;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse_folders(input)
+    }
+
+    fn part_one(folders: &Self::Input) -> Result<Self::PartOne> {
+        Ok(folders.values().filter(|v| v <= &&100000).sum::<u64>())
+    }
+
+    fn part_two(folders: &Self::Input) -> Result<Self::PartTwo> {
+        find_candidate_to_free_space(folders)
+            .ok_or_else(|| Error::no_solution(DAY, 2, "no folder frees enough space"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::solve;
+
+    const EXAMPLE: &str = include_str!("../../data/day_7.example.txt");
+
+    #[test]
+    fn sums_nested_folders() {
+        let folders = parse_folders(EXAMPLE).unwrap();
+        assert_eq!(folders["/"], 48381165);
+        assert_eq!(folders["//a/e"], 584);
+    }
+
+    #[test]
+    fn parses_terminal_lines() {
+        assert_eq!(parse_entry("$ cd a"), Ok(Entry::Cd("a")));
+        assert_eq!(parse_entry("dir e"), Ok(Entry::Dir("e")));
+        assert_eq!(parse_entry("14848514 b.txt"), Ok(Entry::File(14848514)));
+        assert_eq!(parse_entry("$ rm -rf").unwrap_err().column, 3);
+    }
 
-    let to_free = find_candidate_to_free_space(&folders);
+    #[test]
+    fn rejects_unknown_commands() {
+        assert!(parse_folders("$ cd /\n$ rm -rf").is_err());
+    }
 
-    println!("Day 7:");
-    println!("  Part 1: {}", sum);
-    println!("  Part 2: {}", to_free);
+    #[test]
+    fn solves_example() {
+        let solved = solve::<Day7>(EXAMPLE).unwrap();
+        assert_eq!(solved.part_one, 95437.into());
+        assert_eq!(solved.part_two, 24933642.into());
+    }
 }
@@ -1,118 +1,121 @@
-type Matrix = Vec<Vec<u32>>;
-
-fn get_rows(with: &Matrix, at: (usize, usize)) -> (Vec<u32>, Vec<u32>) {
-    let column = &with[at.1];
-
-    let before = column[..at.0].to_vec();
-    let after = column[at.0 + 1..].to_vec();
-
-    (before, after)
-}
-
-fn get_columns(with: &Matrix, at: (usize, usize)) -> (Vec<u32>, Vec<u32>) {
-    let before = with[..at.1].iter().map(|x| x[at.0]).collect();
-
-    let after = with[at.1 + 1..].iter().map(|x| x[at.0]).collect();
-
-    (before, after)
+use crate::{
+    error::Result,
+    grid::Grid,
+    point::{Point, Vector},
+    solution::Solution,
+};
+
+const DAY: u8 = 8;
+
+pub fn parse_trees(input: &str) -> Result<Grid<u32>> {
+    Grid::parse(DAY, input, |c| {
+        c.to_digit(10).ok_or("tree height is not a digit")
+    })
 }
 
-fn count(with: impl IntoIterator<Item = u32>, at: u32) -> usize {
-    let mut steps = 0;
+/// Looks from a tree towards the edge, returning how many trees are seen before the view is
+/// blocked, and whether the edge is seen at all.
+fn view(trees: &Grid<u32>, at: Point, step: Vector) -> (u32, bool) {
+    let height = trees[at];
+    let mut seen = 0;
 
-    let iter = with.into_iter();
-    let c = iter.collect::<Vec<_>>();
-    for number in c {
-        steps += 1;
+    for (_, tree) in trees.ray(at, step) {
+        seen += 1;
 
-        if number >= at {
-            break;
+        if *tree >= height {
+            return (seen, false);
         }
     }
 
-    steps
+    (seen, true)
 }
 
-fn count_steps_until_bigger(with: &Vec<u32>, at: u32, rev: bool) -> usize {
-    if rev {
-        count(with.iter().cloned().rev(), at)
-    } else {
-        count(with.clone(), at)
-    }
+pub fn scenic_score(trees: &Grid<u32>, at: Point) -> u32 {
+    Vector::ORTHOGONAL
+        .iter()
+        .map(|step| view(trees, at, *step).0)
+        .product()
 }
 
-fn get_scenic_score(rows: (&Vec<u32>, &Vec<u32>), columns: (&Vec<u32>, &Vec<u32>), at: u32) -> u32 {
-    let left_row = count_steps_until_bigger(rows.0, at, true);
+/// What both parts need to know about the forest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Survey {
+    pub visible: usize,
+    pub best_scenic_score: u32,
+}
 
-    let right_row = count_steps_until_bigger(rows.1, at, false);
+/// Walks every tree once, returning the amount of visible trees and the best scenic score.
+pub fn survey(trees: &Grid<u32>) -> Survey {
+    let mut visible = 0;
+    let mut best_scenic_score = 0;
 
-    let top_column = count_steps_until_bigger(columns.0, at, true);
+    for at in trees.positions() {
+        let views = Vector::ORTHOGONAL.map(|step| view(trees, at, step));
 
-    let bottom_column = count_steps_until_bigger(columns.1, at, false);
+        // Part 1
+        if views.iter().any(|(_, edge)| *edge) {
+            visible += 1;
+        }
 
-    let score = left_row * right_row * top_column * bottom_column;
+        // Part 2
+        let scenic_score = views.iter().map(|(seen, _)| seen).product();
+        best_scenic_score = best_scenic_score.max(scenic_score);
+    }
 
-    score as u32
+    Survey {
+        visible,
+        best_scenic_score,
+    }
 }
 
-pub fn run(input: String) {
-    let lines = input.lines();
+pub struct Day8;
 
-    let mut trees: Vec<Vec<u32>> = Vec::new();
+impl Solution for Day8 {
+    type Input = Survey;
+    type PartOne = usize;
+    type PartTwo = u32;
 
-    for (line_index, line) in lines.enumerate() {
-        for char in line.chars() {
-            if trees.len() <= line_index {
-                trees.push(Vec::new());
-            }
+    // Both parts come out of the same walk, so it is done once here
+    const SOLVES_WHILE_PARSING: bool = true;
 
-            let current_tree = &mut trees[line_index];
-
-            if let Some(number) = char.to_digit(10) {
-                current_tree.push(number);
-            }
-        }
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse_trees(input).map(|trees| survey(&trees))
     }
 
-    let row_size = trees[0].len();
-    let column_size = trees.len();
-
-    let mut visible = (row_size * 2) + (column_size * 2) - 4;
-
-    let mut biggest_senic_score = 0;
-    for column_index in 1..column_size - 1 {
-        let column = &trees[column_index];
-
-        for row_index in 1..row_size - 1 {
-            let current = column[row_index];
-            let (before_row, after_row) = get_rows(&trees, (row_index, column_index));
+    fn part_one(survey: &Self::Input) -> Result<Self::PartOne> {
+        Ok(survey.visible)
+    }
 
-            let less_in_before = before_row.iter().all(|x| x < &current);
-            let less_in_after = after_row.iter().all(|x| x < &current);
+    fn part_two(survey: &Self::Input) -> Result<Self::PartTwo> {
+        Ok(survey.best_scenic_score)
+    }
+}
 
-            let (before_column, after_column) = get_columns(&trees, (row_index, column_index));
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::solve;
 
-            let less_before_column = before_column.iter().all(|x| x < &current);
-            let less_after_column = after_column.iter().all(|x| x < &current);
+    const EXAMPLE: &str = include_str!("../../data/day_8.example.txt");
 
-            // Part 1
-            if less_in_before || less_in_after || less_before_column || less_after_column {
-                visible += 1;
-            }
+    #[test]
+    fn scores_scenic_views() {
+        // The tree of height 5 in the middle of the fourth row
+        let trees = parse_trees(EXAMPLE).unwrap();
+        assert_eq!(scenic_score(&trees, Point::new(2, 3)), 8);
+        assert_eq!(scenic_score(&trees, Point::new(2, 0)), 0);
+    }
 
-            // Part 2
-            let scenic_score = get_scenic_score(
-                (&before_row, &after_row),
-                (&before_column, &after_column),
-                current,
-            );
-            if scenic_score > biggest_senic_score {
-                biggest_senic_score = scenic_score;
-            }
-        }
+    #[test]
+    fn rejects_uneven_rows() {
+        assert!(parse_trees("123\n12").is_err());
+        assert!(parse_trees("1a3").is_err());
     }
 
-    println!("Day 8:");
-    println!("  Part 1: {}", visible);
-    println!("  Part 2: {}", biggest_senic_score)
+    #[test]
+    fn solves_example() {
+        let solved = solve::<Day8>(EXAMPLE).unwrap();
+        assert_eq!(solved.part_one, 21.into());
+        assert_eq!(solved.part_two, 8.into());
+    }
 }
@@ -1,153 +1,173 @@
 use std::collections::HashSet;
 
-#[derive(Debug)]
-enum Direction {
-    Up(u32),
-    Down(u32),
-    Left(u32),
-    Right(u32),
+use crate::{
+    error::{LineError, Result},
+    parse,
+    point::{Direction, Point},
+    solution::Solution,
+};
+
+const DAY: u8 = 9;
+
+/// Moves the head of the rope a few steps in a direction.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Motion {
+    pub direction: Direction,
+    pub steps: u32,
 }
 
-impl Direction {
-    fn steps(&self) -> u32 {
-        match self {
-            Direction::Up(steps) => *steps,
-            Direction::Down(steps) => *steps,
-            Direction::Left(steps) => *steps,
-            Direction::Right(steps) => *steps,
-        }
+impl TryFrom<&str> for Motion {
+    type Error = LineError;
+
+    fn try_from(line: &str) -> std::result::Result<Self, Self::Error> {
+        parse::line(line, |cursor| {
+            let direction = match cursor.word()? {
+                "R" => Direction::Right,
+                "L" => Direction::Left,
+                "U" => Direction::Up,
+                "D" => Direction::Down,
+                _ => return Err(cursor.error_at(1, "invalid command")),
+            };
+            cursor.tag(" ")?;
+
+            Ok(Motion {
+                direction,
+                steps: cursor.uint()?,
+            })
+        })
     }
 }
 
-fn extract_number(number: &[char]) -> u32 {
-    number.iter().collect::<String>().parse::<u32>().unwrap()
+/// Where a knot moves to keep touching the one ahead of it, if it has to move at all.
+pub fn follow(knot: Point, leader: Point) -> Option<Point> {
+    (knot.chebyshev(leader) > 1).then(|| knot.step_towards(leader))
 }
 
-impl From<&str> for Direction {
-    fn from(value: &str) -> Self {
-        let value = value.chars().collect::<Vec<_>>();
+/// A rope whose knots follow the head, tracking every position visited by the last knot.
+pub struct Rope {
+    head: Point,
+    tail: Vec<Point>,
+    visited: HashSet<Point>,
+}
 
-        let value = value.as_slice();
+impl Rope {
+    pub fn new(size: u8) -> Self {
+        if size == 0 {
+            panic!("Rope size must be greater than 0");
+        }
 
-        match value {
-            ['R', ' ', number @ ..] => Direction::Right(extract_number(number)),
-            ['L', ' ', number @ ..] => Direction::Left(extract_number(number)),
-            ['U', ' ', number @ ..] => Direction::Up(extract_number(number)),
-            ['D', ' ', number @ ..] => Direction::Down(extract_number(number)),
-            _ => panic!("Invalid command: {:?}", value),
+        Rope {
+            visited: HashSet::from([Point::ORIGIN]),
+            head: Point::ORIGIN,
+            tail: vec![Point::ORIGIN; size as usize],
         }
     }
-}
 
-struct Rope {
-    head: Knot,
-    tail: Vec<Knot>,
-    visited: HashSet<(i32, i32)>,
-}
-
-#[derive(Debug)]
-struct Knot(i32, i32);
-
-impl Knot {
-    fn new() -> Self {
-        Self(0, 0)
+    pub fn visited(&self) -> &HashSet<Point> {
+        &self.visited
     }
 
-    fn move_once(&mut self, direction: &Direction) {
-        match direction {
-            Direction::Up(_) => self.0 += 1,
-            Direction::Down(_) => self.0 -= 1,
-            Direction::Left(_) => self.1 -= 1,
-            Direction::Right(_) => self.1 += 1,
-        }
+    pub fn last_body(&self) -> Point {
+        *self.tail.last().unwrap()
     }
 
-    fn into_tuple(&self) -> (i32, i32) {
-        (self.0, self.1)
+    pub fn trasverse_commands(&mut self, motions: &[Motion]) {
+        for motion in motions {
+            self.move_rope(motion);
+        }
     }
 
-    fn adjust_position(&self, reference: &Knot) -> Option<Knot> {
-        let (x1, y1) = self.into_tuple();
-        let (x2, y2) = reference.into_tuple();
+    pub fn move_rope(&mut self, motion: &Motion) {
+        for _ in 0..motion.steps {
+            self.head += motion.direction.vector();
 
-        let x = (x2 as f64 - x1 as f64).abs();
-        let y = (y2 as f64 - y1 as f64).abs();
-
-        let new_x = if x1 < x2 { x2 - 1 } else { x2 + 1 };
-
-        let new_y = if y1 < y2 { y2 - 1 } else { y2 + 1 };
+            let mut leader = self.head;
+            for knot in self.tail.iter_mut() {
+                if let Some(position) = follow(*knot, leader) {
+                    *knot = position;
+                }
+                leader = *knot;
+            }
 
-        match (x, y) {
-            (x, y) if x >= 2.0 && y >= 2.0 => Some(Knot(new_x, new_y)),
-            (x, _) if x >= 2.0 => Some(Knot(new_x, y2)),
-            (_, y) if y >= 2.0 => Some(Knot(x2, new_y)),
-            _ => None,
+            self.visited.insert(self.last_body());
         }
     }
 }
 
-impl Rope {
-    fn new(size: u8) -> Self {
-        if size == 0 {
-            panic!("Rope size must be greater than 0");
-        }
+pub struct Day9;
 
-        let visited = HashSet::from_iter([(0, 0)]);
-        let tail = (0..size).into_iter().map(|_| Knot::new()).collect();
+impl Solution for Day9 {
+    type Input = Vec<Motion>;
+    type PartOne = usize;
+    type PartTwo = usize;
 
-        Rope {
-            visited,
-            head: Knot::new(),
-            tail,
-        }
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse::each_line(DAY, input, Motion::try_from)
     }
 
-    fn last_body(&self) -> &Knot {
-        self.tail.last().unwrap()
+    fn part_one(commands: &Self::Input) -> Result<Self::PartOne> {
+        let mut rope = Rope::new(1);
+        rope.trasverse_commands(commands);
+        Ok(rope.visited().len())
     }
 
-    fn trasverse_commands(&mut self, commands: &Vec<Direction>) {
-        for command in commands {
-            self.move_rope(command);
-        }
+    fn part_two(commands: &Self::Input) -> Result<Self::PartTwo> {
+        let mut rope = Rope::new(9);
+        rope.trasverse_commands(commands);
+        Ok(rope.visited().len())
     }
+}
 
-    fn move_rope(&mut self, direction: &Direction) {
-        let steps = direction.steps();
-        for _ in 0..steps {
-            self.head.move_once(direction);
-
-            for i in 0..self.tail.len() {
-                let updated_position = {
-                    let before = if i > 0 { &self.tail[i - 1] } else { &self.head };
-
-                    self.tail[i].adjust_position(before)
-                };
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::solve;
 
-                if let Some(pos) = updated_position {
-                    self.tail[i] = pos;
-                }
-            }
+    const EXAMPLE: &str = include_str!("../../data/day_9.example.txt");
+    const LARGER_EXAMPLE: &str = include_str!("../../data/day_9.example.2.txt");
 
-            self.visited.insert(self.last_body().into_tuple());
-        }
+    #[test]
+    fn knots_stay_when_touching() {
+        assert_eq!(follow(Point::ORIGIN, Point::new(1, 1)), None);
+        assert_eq!(follow(Point::ORIGIN, Point::ORIGIN), None);
     }
-}
 
-pub fn run(input: String) {
-    let commands = input
-        .lines()
-        .into_iter()
-        .map(Direction::from)
-        .collect::<Vec<_>>();
+    #[test]
+    fn knots_follow_straight_and_diagonally() {
+        let follow = |x, y| follow(Point::ORIGIN, Point::new(x, y));
+        assert_eq!(follow(2, 0), Some(Point::new(1, 0)));
+        assert_eq!(follow(0, -2), Some(Point::new(0, -1)));
+        assert_eq!(follow(2, 1), Some(Point::new(1, 1)));
+        assert_eq!(follow(2, 2), Some(Point::new(1, 1)));
+    }
 
-    let mut part_1 = Rope::new(1);
-    part_1.trasverse_commands(&commands);
+    #[test]
+    fn parses_motions() {
+        assert_eq!(
+            Motion::try_from("R 4"),
+            Ok(Motion {
+                direction: Direction::Right,
+                steps: 4
+            })
+        );
+        assert_eq!(
+            Motion::try_from("D 12"),
+            Ok(Motion {
+                direction: Direction::Down,
+                steps: 12
+            })
+        );
+        assert_eq!(Motion::try_from("X 1").unwrap_err().column, 1);
+        assert_eq!(Motion::try_from("U x").unwrap_err().column, 3);
+    }
 
-    let mut part_2 = Rope::new(9);
-    part_2.trasverse_commands(&commands);
+    #[test]
+    fn solves_examples() {
+        let solved = solve::<Day9>(EXAMPLE).unwrap();
+        assert_eq!(solved.part_one, 13.into());
+        assert_eq!(solved.part_two, 1.into());
 
-    println!("Day 9:");
-    println!("  Part 1: {}", part_1.visited.len());
-    println!("  Part 2: {}", part_2.visited.len());
+        let solved = solve::<Day9>(LARGER_EXAMPLE).unwrap();
+        assert_eq!(solved.part_two, 36.into());
+    }
 }
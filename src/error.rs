@@ -0,0 +1,133 @@
+use std::{fmt, io, path::PathBuf};
+
+use crate::input::Issue;
+
+/// Every way a day can fail, from reading its input to solving it.
+#[derive(Debug)]
+pub enum Error {
+    Io {
+        path: PathBuf,
+        source: io::Error,
+    },
+    Parse {
+        day: u8,
+        line: usize,
+        column: usize,
+        text: String,
+        reason: String,
+    },
+    NoSolution {
+        day: u8,
+        part: u8,
+        reason: String,
+    },
+    Config {
+        path: PathBuf,
+        line: usize,
+        reason: String,
+    },
+    /// The input of a day cannot be solved, found before handing it to the day.
+    Input {
+        day: u8,
+        issue: Issue,
+    },
+    /// Downloading an input failed, `day` being unknown while still setting up the download.
+    Fetch {
+        day: Option<u8>,
+        reason: String,
+    },
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl Error {
+    pub fn no_solution(day: u8, part: u8, reason: impl Into<String>) -> Self {
+        Error::NoSolution {
+            day,
+            part,
+            reason: reason.into(),
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io { path, source } => {
+                write!(f, "could not read {}: {}", path.display(), source)
+            }
+            Error::Parse {
+                day,
+                line,
+                column,
+                text,
+                reason,
+            } => write!(
+                f,
+                "day {}, line {}, column {}: {} in `{}`",
+                day, line, column, reason, text
+            ),
+            Error::NoSolution { day, part, reason } => {
+                write!(f, "day {}, part {}: {}", day, part, reason)
+            }
+            Error::Config { path, line, reason } => {
+                write!(f, "{}, line {}: {}", path.display(), line, reason)
+            }
+            Error::Input { day, issue } => write!(f, "day {}: {}", day, issue),
+            Error::Fetch {
+                day: Some(day),
+                reason,
+            } => write!(f, "day {}: could not fetch the input: {}", day, reason),
+            Error::Fetch { day: None, reason } => write!(f, "could not fetch inputs: {}", reason),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/// A parse failure inside a single line, before knowing which day and line it belongs to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineError {
+    pub column: usize,
+    pub text: String,
+    pub reason: String,
+}
+
+impl LineError {
+    /// `column` is 1-based, as shown to the user.
+    pub fn new(column: usize, text: &str, reason: impl Into<String>) -> Self {
+        Self {
+            column,
+            text: text.to_string(),
+            reason: reason.into(),
+        }
+    }
+
+    /// Places the error in the input, `line` being 1-based.
+    pub fn at(self, day: u8, line: usize) -> Error {
+        Error::Parse {
+            day,
+            line,
+            column: self.column,
+            text: self.text,
+            reason: self.reason,
+        }
+    }
+}
+
+impl fmt::Display for LineError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "column {}: {} in `{}`",
+            self.column, self.reason, self.text
+        )
+    }
+}
@@ -0,0 +1,330 @@
+use std::{
+    env, fs,
+    io::{Read, Write},
+    net::TcpStream,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use crate::{
+    error::{Error, Result},
+    input::Source,
+};
+
+/// Base URL of the puzzles, inputs being fetched from `<base>/day/<N>/input`.
+pub const URL_VAR: &str = "AOC_URL";
+/// Value of the `session` cookie identifying the account the inputs belong to.
+pub const SESSION_VAR: &str = "AOC_SESSION";
+
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Whether an input had to be downloaded or was already in the cache.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Fetched {
+    Downloaded(PathBuf),
+    Cached(PathBuf),
+}
+
+/// A minimal HTTP client downloading the inputs of an account.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fetcher {
+    host: String,
+    port: u16,
+    path: String,
+    session: String,
+}
+
+impl Fetcher {
+    /// Only plain `http://` URLs are supported, e.g. `http://localhost:8080/2022`. Both the URL and
+    /// the session end up in the request headers, so neither may hold line breaks.
+    pub fn new(base_url: &str, session: impl Into<String>) -> Result<Self> {
+        let session = session.into();
+        if session.is_empty() || session.contains(|c: char| c.is_control()) {
+            return Err(Error::Fetch {
+                day: None,
+                reason: "invalid session: empty or holding control characters".to_string(),
+            });
+        }
+
+        let invalid = |reason: &str| Error::Fetch {
+            day: None,
+            reason: format!("invalid URL `{}`: {}", base_url.escape_debug(), reason),
+        };
+
+        if base_url.contains(|c: char| c.is_control() || c == ' ') {
+            return Err(invalid("control characters and spaces are not allowed"));
+        }
+
+        let rest = base_url
+            .strip_prefix("http://")
+            .ok_or_else(|| invalid("only plain http:// is supported"))?;
+
+        let (authority, path) = match rest.find('/') {
+            Some(index) => rest.split_at(index),
+            None => (rest, ""),
+        };
+
+        let (host, port) = match authority.split_once(':') {
+            Some((host, port)) => (host, port.parse().map_err(|_| invalid("invalid port"))?),
+            None => (authority, 80),
+        };
+
+        if host.is_empty() {
+            return Err(invalid("missing host"));
+        }
+
+        Ok(Self {
+            host: host.to_string(),
+            port,
+            path: path.trim_end_matches('/').to_string(),
+            session,
+        })
+    }
+
+    /// Reads the base URL and the session from `AOC_URL` and `AOC_SESSION`, unless given.
+    pub fn from_env(base_url: Option<&str>, session: Option<&str>) -> Result<Self> {
+        let setting = |value: Option<&str>, var: &str, what: &str| {
+            value
+                .map(String::from)
+                .or_else(|| env::var(var).ok())
+                .ok_or_else(|| Error::Fetch {
+                    day: None,
+                    reason: format!("no {} given, set {} or pass it as a flag", what, var),
+                })
+        };
+
+        Self::new(
+            &setting(base_url, URL_VAR, "base URL")?,
+            setting(session, SESSION_VAR, "session")?,
+        )
+    }
+
+    /// Downloads the input of a day, without caching it.
+    pub fn fetch(&self, day: u8) -> Result<String> {
+        let error = |reason: String| Error::Fetch {
+            day: Some(day),
+            reason,
+        };
+
+        let mut stream = TcpStream::connect((self.host.as_str(), self.port))
+            .map_err(|e| error(format!("could not connect to {}: {}", self.host, e)))?;
+        stream
+            .set_read_timeout(Some(TIMEOUT))
+            .and_then(|_| stream.set_write_timeout(Some(TIMEOUT)))
+            .map_err(|e| error(e.to_string()))?;
+
+        let request = format!(
+            "GET {}/day/{}/input HTTP/1.1\r\n\
+             Host: {}\r\n\
+             Cookie: session={}\r\n\
+             User-Agent: advent-of-code-22 input fetcher\r\n\
+             Connection: close\r\n\r\n",
+            self.path, day, self.host, self.session
+        );
+
+        let mut response = Vec::new();
+        stream
+            .write_all(request.as_bytes())
+            .and_then(|_| stream.read_to_end(&mut response))
+            .map_err(|e| error(format!("request failed: {}", e)))?;
+
+        parse_response(&response).map_err(error)
+    }
+
+    /// Stores the input of a day in `dir`, unless a non-empty copy is already there.
+    pub fn download(&self, day: u8, dir: &Path) -> Result<Fetched> {
+        let path = dir.join(Source::Real.file_name(day));
+
+        if fs::metadata(&path).is_ok_and(|m| m.len() > 0) {
+            return Ok(Fetched::Cached(path));
+        }
+
+        let input = self.fetch(day)?;
+
+        fs::create_dir_all(dir)
+            .and_then(|_| fs::write(&path, input))
+            .map_err(|e| Error::Fetch {
+                day: Some(day),
+                reason: format!("could not write {}: {}", path.display(), e),
+            })?;
+
+        Ok(Fetched::Downloaded(path))
+    }
+}
+
+/// Extracts the body of a successful HTTP/1.1 response.
+fn parse_response(response: &[u8]) -> std::result::Result<String, String> {
+    let response = String::from_utf8_lossy(response);
+
+    let (head, body) = response
+        .split_once("\r\n\r\n")
+        .ok_or("incomplete response from the server")?;
+    let mut lines = head.lines();
+
+    let status = lines.next().unwrap_or_default();
+    match status.split_whitespace().nth(1) {
+        Some("200") => {}
+        Some(_) => {
+            let reason = status
+                .split_once(' ')
+                .map(|(_, rest)| rest)
+                .unwrap_or_default();
+            return Err(format!("the server answered {}", reason));
+        }
+        None => return Err(format!("invalid status line `{}`", status)),
+    }
+
+    let chunked = lines.any(|line| {
+        line.split_once(':').is_some_and(|(name, value)| {
+            name.eq_ignore_ascii_case("transfer-encoding") && value.trim() == "chunked"
+        })
+    });
+
+    if chunked {
+        decode_chunked(body)
+    } else {
+        Ok(body.to_string())
+    }
+}
+
+/// Joins the chunks of a body sent with `Transfer-Encoding: chunked`.
+fn decode_chunked(mut body: &str) -> std::result::Result<String, String> {
+    let mut decoded = String::new();
+
+    loop {
+        let (size, rest) = body.split_once("\r\n").ok_or("truncated chunk")?;
+        let size = size.split(';').next().unwrap_or_default().trim();
+        let size = usize::from_str_radix(size, 16)
+            .map_err(|_| format!("invalid chunk size `{}`", size))?;
+
+        if size == 0 {
+            return Ok(decoded);
+        }
+
+        let chunk = rest.get(..size).ok_or("truncated chunk")?;
+        decoded.push_str(chunk);
+        body = rest[size..].strip_prefix("\r\n").ok_or("truncated chunk")?;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{io::BufRead, io::BufReader, net::TcpListener, thread};
+
+    /// Serves a single request with `response`, returning the base URL and the request received.
+    fn serve_once(response: &'static str) -> (String, thread::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/2022", listener.local_addr().unwrap());
+
+        let server = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = String::new();
+
+            let mut reader = BufReader::new(&mut stream);
+            while !request.ends_with("\r\n\r\n") {
+                reader.read_line(&mut request).unwrap();
+            }
+
+            stream.write_all(response.as_bytes()).unwrap();
+            request
+        });
+
+        (url, server)
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("aoc-fetch-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn parses_base_urls() {
+        let fetcher = Fetcher::new("http://localhost:8080/2022/", "abc").unwrap();
+        assert_eq!(fetcher.host, "localhost");
+        assert_eq!(fetcher.port, 8080);
+        assert_eq!(fetcher.path, "/2022");
+
+        assert_eq!(Fetcher::new("http://example.com", "abc").unwrap().port, 80);
+        assert!(Fetcher::new("https://example.com", "abc").is_err());
+        assert!(Fetcher::new("http://:80", "abc").is_err());
+        assert!(Fetcher::new("http://example.com/2022\r\nX-Evil: 1", "abc").is_err());
+    }
+
+    #[test]
+    fn rejects_sessions_injecting_headers() {
+        let url = "http://localhost:8080/2022";
+        assert!(Fetcher::new(url, "abc\r\nX-Evil: 1").is_err());
+        assert!(Fetcher::new(url, "abc\n").is_err());
+        assert!(Fetcher::new(url, "").is_err());
+        assert_eq!(
+            Fetcher::new(url, "53616c7465645f5f").unwrap().session,
+            "53616c7465645f5f"
+        );
+    }
+
+    #[test]
+    fn downloads_and_caches_inputs() {
+        let (url, server) = serve_once("HTTP/1.1 200 OK\r\nContent-Length: 8\r\n\r\n1\n2\n\n3\n\n");
+        let dir = temp_dir("download");
+        let fetcher = Fetcher::new(&url, "secret").unwrap();
+
+        let path = dir.join("day_1.txt");
+        assert_eq!(
+            fetcher.download(1, &dir).unwrap(),
+            Fetched::Downloaded(path.clone())
+        );
+        assert_eq!(fs::read_to_string(&path).unwrap(), "1\n2\n\n3\n\n");
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("GET /2022/day/1/input HTTP/1.1\r\n"));
+        assert!(request.contains("Cookie: session=secret\r\n"));
+
+        // The server is gone, so this only works from the cache
+        assert_eq!(fetcher.download(1, &dir).unwrap(), Fetched::Cached(path));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn replaces_empty_cached_inputs() {
+        let (url, server) = serve_once("HTTP/1.1 200 OK\r\n\r\nabc\n");
+        let dir = temp_dir("empty");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("day_6.txt"), "").unwrap();
+
+        let fetcher = Fetcher::new(&url, "secret").unwrap();
+        assert!(matches!(
+            fetcher.download(6, &dir),
+            Ok(Fetched::Downloaded(_))
+        ));
+        server.join().unwrap();
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn reports_server_errors() {
+        let (url, server) = serve_once("HTTP/1.1 404 Not Found\r\n\r\nPlease don't repeatedly request this endpoint before it unlocks!");
+        let dir = temp_dir("error");
+
+        let error = Fetcher::new(&url, "secret")
+            .unwrap()
+            .download(25, &dir)
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "day 25: could not fetch the input: the server answered 404 Not Found"
+        );
+        assert!(!dir.exists());
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn decodes_chunked_bodies() {
+        let response = b"HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n4\r\n1000\r\n3\r\n\n20\r\n0\r\n\r\n";
+        assert_eq!(parse_response(response), Ok("1000\n20".to_string()));
+        assert!(decode_chunked("4\r\n10").is_err());
+    }
+}
@@ -0,0 +1,269 @@
+use std::{
+    fmt,
+    ops::{Index, IndexMut},
+};
+
+use crate::{
+    error::{LineError, Result},
+    point::{Point, Vector},
+};
+
+/// A rectangular grid of cells, stored row after row. `Point::ORIGIN` is the top left corner.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self {
+        Self {
+            cells: vec![fill; width * height],
+            width,
+            height,
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    /// Parses a map with a character per cell, failing on empty or uneven rows. `cell` tells why
+    /// a character is not valid.
+    pub fn parse(
+        day: u8,
+        input: &str,
+        mut cell: impl FnMut(char) -> std::result::Result<T, &'static str>,
+    ) -> Result<Self> {
+        let mut cells = Vec::new();
+        let mut width = None;
+        let mut height = 0;
+
+        for (index, line) in input.lines().enumerate() {
+            let error = |column, reason| LineError::new(column, line, reason).at(day, index + 1);
+
+            let before = cells.len();
+            for (column, char) in line.chars().enumerate() {
+                cells.push(cell(char).map_err(|reason| error(column + 1, reason))?);
+            }
+
+            let row_width = cells.len() - before;
+            if row_width == 0 {
+                return Err(error(1, "empty row"));
+            }
+
+            let expected = *width.get_or_insert(row_width);
+            if expected != row_width {
+                // Points at the first missing or extra cell
+                let column = row_width.min(expected) + 1;
+                return Err(error(column, "rows have different lengths"));
+            }
+
+            height += 1;
+        }
+
+        match width {
+            Some(width) => Ok(Self {
+                cells,
+                width,
+                height,
+            }),
+            None => Err(LineError::new(1, "", "empty map").at(day, 1)),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    fn index_of(&self, at: Point) -> Option<usize> {
+        let x = usize::try_from(at.x).ok().filter(|x| *x < self.width)?;
+        let y = usize::try_from(at.y).ok().filter(|y| *y < self.height)?;
+        Some(y * self.width + x)
+    }
+
+    pub fn contains(&self, at: Point) -> bool {
+        self.index_of(at).is_some()
+    }
+
+    pub fn get(&self, at: Point) -> Option<&T> {
+        self.index_of(at).map(|index| &self.cells[index])
+    }
+
+    pub fn get_mut(&mut self, at: Point) -> Option<&mut T> {
+        self.index_of(at).map(|index| &mut self.cells[index])
+    }
+
+    /// Moves `from` by `step`, if it stays inside the grid.
+    pub fn step(&self, from: Point, step: Vector) -> Option<Point> {
+        let to = from + step;
+        self.contains(to).then_some(to)
+    }
+
+    /// Every position, row after row.
+    pub fn positions(&self) -> impl Iterator<Item = Point> + '_ {
+        (0..self.height)
+            .flat_map(move |y| (0..self.width).map(move |x| Point::new(x as i64, y as i64)))
+    }
+
+    /// Every cell along with its position, row after row.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.positions().zip(&self.cells)
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn row(&self, y: usize) -> Option<&[T]> {
+        (y < self.height).then(|| &self.cells[y * self.width..(y + 1) * self.width])
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        let cells = if x < self.width {
+            &self.cells[x..]
+        } else {
+            &[]
+        };
+        cells.iter().step_by(self.width.max(1))
+    }
+
+    /// The cells met walking from `from` by `step` until leaving the grid, `from` excluded.
+    pub fn ray(&self, from: Point, step: Vector) -> impl Iterator<Item = (Point, &T)> {
+        std::iter::successors(self.step(from, step), move |at| self.step(*at, step))
+            .map(|at| (at, &self[at]))
+    }
+
+    /// The positions next to `at` in the given directions, inside the grid.
+    pub fn neighbours<'a>(
+        &'a self,
+        at: Point,
+        directions: &'a [Vector],
+    ) -> impl Iterator<Item = Point> + 'a {
+        directions
+            .iter()
+            .filter_map(move |step| self.step(at, *step))
+    }
+
+    /// The positions above, below, left and right of `at`.
+    pub fn neighbours4(&self, at: Point) -> impl Iterator<Item = Point> + '_ {
+        self.neighbours(at, &Vector::ORTHOGONAL)
+    }
+
+    /// The positions around `at`, diagonals included.
+    pub fn neighbours8(&self, at: Point) -> impl Iterator<Item = Point> + '_ {
+        self.neighbours(at, &Vector::ALL_DIRECTIONS)
+    }
+
+    /// Position of the first cell matching `predicate`, row after row.
+    pub fn find(&self, mut predicate: impl FnMut(&T) -> bool) -> Option<Point> {
+        self.iter()
+            .find(|(_, cell)| predicate(cell))
+            .map(|(at, _)| at)
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, at: Point) -> &T {
+        self.get(at)
+            .unwrap_or_else(|| panic!("{} is outside of the grid", at))
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, at: Point) -> &mut T {
+        self.get_mut(at)
+            .unwrap_or_else(|| panic!("{} is outside of the grid", at))
+    }
+}
+
+/// Draws the cells row after row, without separators.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, row) in self.rows().enumerate() {
+            if index > 0 {
+                writeln!(f)?;
+            }
+
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digits(input: &str) -> Result<Grid<u32>> {
+        Grid::parse(0, input, |c| c.to_digit(10).ok_or("not a digit"))
+    }
+
+    #[test]
+    fn parses_character_maps() {
+        let grid = digits("123\n456\n").unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.get(Point::new(0, 1)), Some(&4));
+        assert_eq!(grid.get(Point::new(0, 2)), None);
+        assert_eq!(grid.get(Point::new(3, 0)), None);
+        assert_eq!(grid.get(Point::new(-1, 0)), None);
+        assert_eq!(grid.to_string(), "123\n456");
+    }
+
+    #[test]
+    fn rejects_invalid_maps() {
+        assert_eq!(
+            digits("123\n4a6").unwrap_err().to_string(),
+            "day 0, line 2, column 2: not a digit in `4a6`"
+        );
+        assert!(digits("123\n45").is_err());
+        assert!(digits("123\n4567").is_err());
+        assert!(digits("123\n\n456").is_err());
+        assert!(digits("").is_err());
+    }
+
+    #[test]
+    fn walks_rows_columns_and_rays() {
+        let grid = digits("123\n456\n789").unwrap();
+        assert_eq!(grid.row(1), Some(&[4, 5, 6][..]));
+        assert_eq!(grid.row(3), None);
+        assert_eq!(grid.column(2).copied().collect::<Vec<_>>(), vec![3, 6, 9]);
+        assert_eq!(grid.column(3).count(), 0);
+        assert_eq!(grid.rows().count(), 3);
+
+        let ray = grid
+            .ray(Point::new(0, 2), Vector::new(1, -1))
+            .collect::<Vec<_>>();
+        assert_eq!(ray, vec![(Point::new(1, 1), &5), (Point::new(2, 0), &3)]);
+        assert_eq!(grid.ray(Point::ORIGIN, Vector::UP).count(), 0);
+    }
+
+    #[test]
+    fn finds_neighbours_inside_the_grid() {
+        let grid = Grid::new(3, 2, 0);
+        assert_eq!(
+            grid.neighbours4(Point::ORIGIN).collect::<Vec<_>>(),
+            vec![Point::new(0, 1), Point::new(1, 0)]
+        );
+        assert_eq!(grid.neighbours8(Point::new(1, 1)).count(), 5);
+        assert_eq!(grid.neighbours8(Point::new(1, 0)).count(), 5);
+    }
+
+    #[test]
+    fn updates_cells() {
+        let mut grid = Grid::new(2, 2, '.');
+        grid[Point::new(0, 1)] = '#';
+        *grid.get_mut(Point::new(1, 0)).unwrap() = '#';
+        assert!(grid.get_mut(Point::new(0, 2)).is_none());
+        assert_eq!(grid.to_string(), ".#\n#.");
+        assert_eq!(grid.find(|c| *c == '#'), Some(Point::new(1, 0)));
+    }
+}
@@ -0,0 +1,239 @@
+use std::{
+    env, fmt, fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+};
+
+use crate::error::{Error, Result};
+
+/// Overrides the folder holding the inputs and the answers file.
+pub const DATA_DIR_VAR: &str = "AOC_DATA_DIR";
+
+/// The folder holding the inputs, `AOC_DATA_DIR` or the `data` folder of this crate, so the
+/// binary works from any working directory.
+pub fn data_dir() -> PathBuf {
+    env::var_os(DATA_DIR_VAR)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| Path::new(env!("CARGO_MANIFEST_DIR")).join("data"))
+}
+
+/// Which input of a day to use. Examples are numbered from 1 and live next to the real input:
+/// `day_5.example.txt` is the first one, `day_5.example.2.txt` the second, and so on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Source {
+    Real,
+    Example(usize),
+}
+
+impl Source {
+    fn stem(&self, day: u8) -> String {
+        match self {
+            Source::Real => format!("day_{}", day),
+            Source::Example(1) => format!("day_{}.example", day),
+            Source::Example(n) => format!("day_{}.example.{}", day, n),
+        }
+    }
+
+    pub fn file_name(&self, day: u8) -> String {
+        format!("{}.txt", self.stem(day))
+    }
+
+    /// Name of the table holding the answers of this input in the answers file.
+    pub fn section(&self, day: u8) -> String {
+        self.stem(day)
+    }
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Source::Real => write!(f, "input"),
+            Source::Example(n) => write!(f, "example {}", n),
+        }
+    }
+}
+
+pub fn path(day: u8, source: Source) -> PathBuf {
+    data_dir().join(source.file_name(day))
+}
+
+pub fn read(day: u8, source: Source) -> Result<String> {
+    read_path(&path(day, source))
+}
+
+/// Reads an input from any path, `-` meaning the standard input.
+pub fn read_path(path: &Path) -> Result<String> {
+    if path == Path::new("-") {
+        let mut input = String::new();
+        return io::stdin()
+            .read_to_string(&mut input)
+            .map(|_| input)
+            .map_err(|source| Error::Io {
+                path: path.to_path_buf(),
+                source,
+            });
+    }
+
+    fs::read_to_string(path).map_err(|source| Error::Io {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+/// Something wrong with an input, usually left behind when copying it from the browser.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Issue {
+    Missing,
+    Unreadable(String),
+    Empty,
+    /// Amount of lines ending with `\r\n` instead of `\n`.
+    CrLf(usize),
+    /// First line ending with spaces or tabs, and the amount of such lines.
+    TrailingWhitespace {
+        line: usize,
+        lines: usize,
+    },
+    /// Amount of blank lines after the last line with content.
+    TrailingBlankLines(usize),
+}
+
+impl Issue {
+    /// Whether the input cannot be solved at all, rather than possibly being misread.
+    pub fn is_fatal(&self) -> bool {
+        matches!(self, Issue::Missing | Issue::Unreadable(_) | Issue::Empty)
+    }
+}
+
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Issue::Missing => write!(f, "the input is missing"),
+            Issue::Unreadable(reason) => write!(f, "the input cannot be read: {}", reason),
+            Issue::Empty => write!(f, "the input is empty"),
+            Issue::CrLf(lines) => write!(f, "{} lines end with CRLF", lines),
+            Issue::TrailingWhitespace { line, lines: 1 } => {
+                write!(f, "line {} ends with whitespace", line)
+            }
+            Issue::TrailingWhitespace { line, lines } => write!(
+                f,
+                "{} lines end with whitespace, the first being line {}",
+                lines, line
+            ),
+            Issue::TrailingBlankLines(lines) => {
+                write!(f, "{} blank lines after the end of the input", lines)
+            }
+        }
+    }
+}
+
+/// Every issue found in an input.
+pub fn diagnose(input: &str) -> Vec<Issue> {
+    if input.trim().is_empty() {
+        return vec![Issue::Empty];
+    }
+
+    let mut issues = Vec::new();
+
+    let crlf = input.matches("\r\n").count();
+    if crlf > 0 {
+        issues.push(Issue::CrLf(crlf));
+    }
+
+    let mut trailing = input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| line.ends_with([' ', '\t']))
+        .map(|(index, _)| index + 1);
+    if let Some(line) = trailing.next() {
+        issues.push(Issue::TrailingWhitespace {
+            line,
+            lines: trailing.count() + 1,
+        });
+    }
+
+    let blank = input
+        .lines()
+        .rev()
+        .take_while(|line| line.trim().is_empty())
+        .count();
+    if blank > 0 {
+        issues.push(Issue::TrailingBlankLines(blank));
+    }
+
+    issues
+}
+
+/// Every issue found in an input file, including it not being there.
+pub fn diagnose_path(path: &Path) -> Vec<Issue> {
+    match fs::read_to_string(path) {
+        Ok(input) => diagnose(&input),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => vec![Issue::Missing],
+        Err(e) => vec![Issue::Unreadable(e.to_string())],
+    }
+}
+
+/// Every example found for a day, in order.
+pub fn examples(day: u8) -> Vec<Source> {
+    let first = format!("day_{}.example.txt", day);
+    let prefix = format!("day_{}.example.", day);
+
+    let mut examples = fs::read_dir(data_dir())
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().into_string().ok()?;
+
+            if name == first {
+                return Some(Source::Example(1));
+            }
+
+            name.strip_prefix(&prefix)?
+                .strip_suffix(".txt")?
+                .parse()
+                .ok()
+                .filter(|n| *n > 1)
+                .map(Source::Example)
+        })
+        .collect::<Vec<_>>();
+
+    examples.sort();
+    examples
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepts_clean_inputs() {
+        assert_eq!(diagnose("1000\n2000\n\n3000\n"), vec![]);
+        assert_eq!(diagnose("1000\n2000"), vec![]);
+    }
+
+    #[test]
+    fn finds_empty_inputs() {
+        assert_eq!(diagnose(""), vec![Issue::Empty]);
+        assert_eq!(diagnose(" \n\n"), vec![Issue::Empty]);
+        assert!(Issue::Empty.is_fatal());
+    }
+
+    #[test]
+    fn finds_copy_paste_accidents() {
+        assert_eq!(
+            diagnose("1000 \r\n2000\r\n3000\t\n\n\n"),
+            vec![
+                Issue::CrLf(2),
+                Issue::TrailingWhitespace { line: 1, lines: 2 },
+                Issue::TrailingBlankLines(2),
+            ]
+        );
+        assert!(!Issue::CrLf(2).is_fatal());
+    }
+
+    #[test]
+    fn finds_missing_files() {
+        let path = data_dir().join("day_0.txt");
+        assert_eq!(diagnose_path(&path), vec![Issue::Missing]);
+    }
+}
@@ -0,0 +1,152 @@
+use std::{fmt::Write, time::Duration};
+
+use advent_of_code_22::{
+    error::Result,
+    solution::{Answer, Solved},
+};
+
+fn string(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len() + 2);
+    escaped.push('"');
+
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(escaped, "\\u{:04x}", c as u32);
+            }
+            c => escaped.push(c),
+        }
+    }
+
+    escaped.push('"');
+    escaped
+}
+
+fn answer(answer: &Answer) -> (&'static str, String) {
+    match answer {
+        Answer::Number(n) => ("number", n.to_string()),
+        Answer::Text(s) => ("string", string(s)),
+        Answer::Image(rows) => {
+            let rows = rows.iter().map(|row| string(row)).collect::<Vec<_>>();
+            ("image", format!("[{}]", rows.join(", ")))
+        }
+    }
+}
+
+fn part(number: u8, value: &Answer, time: Duration) -> String {
+    let (kind, value) = answer(value);
+    format!(
+        "{{\"part\": {}, \"type\": \"{}\", \"answer\": {}, \"time_ns\": {}}}",
+        number,
+        kind,
+        value,
+        time.as_nanos()
+    )
+}
+
+fn day(day: u8, input: &str, result: &Result<Solved>) -> String {
+    let mut object = format!("{{\"day\": {}, \"input\": {}", day, string(input));
+
+    match result {
+        Ok(solved) => {
+            let timings = &solved.timings;
+            let parts = [
+                part(1, &solved.part_one, timings.part_one),
+                part(2, &solved.part_two, timings.part_two),
+            ];
+
+            let _ = write!(
+                object,
+                ", \"parse_ns\": {}, \"total_ns\": {}, \"parts\": [{}]}}",
+                timings.parse.as_nanos(),
+                timings.total().as_nanos(),
+                parts.join(", ")
+            );
+        }
+        Err(e) => {
+            let _ = write!(object, ", \"error\": {}}}", string(&e.to_string()));
+        }
+    }
+
+    object
+}
+
+/// Renders the results of a run as a JSON document, one object per executed input.
+pub fn render(results: &[(u8, String, Result<Solved>)]) -> String {
+    let days = results
+        .iter()
+        .map(|(d, input, result)| format!("    {}", day(*d, input, result)))
+        .collect::<Vec<_>>();
+
+    if days.is_empty() {
+        return "{\n  \"days\": []\n}".to_string();
+    }
+
+    format!("{{\n  \"days\": [\n{}\n  ]\n}}", days.join(",\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use advent_of_code_22::{error::Error, solution::Timings};
+
+    #[test]
+    fn escapes_strings() {
+        assert_eq!(string(r#"say "hi" \ bye"#), r#""say \"hi\" \\ bye""#);
+        assert_eq!(string("a\nb\r\tc"), r#""a\nb\r\tc""#);
+        assert_eq!(string("\u{0}\u{1f}"), r#""\u0000\u001f""#);
+        assert_eq!(string("█░ é"), "\"█░ é\"");
+    }
+
+    #[test]
+    fn renders_every_kind_of_answer() {
+        assert_eq!(
+            answer(&Answer::from(24000)),
+            ("number", "24000".to_string())
+        );
+        assert_eq!(
+            answer(&Answer::Text("CMZ".to_string())),
+            ("string", "\"CMZ\"".to_string())
+        );
+
+        let image = Answer::Image(vec!["█░".to_string(), "░█".to_string()]);
+        assert_eq!(answer(&image), ("image", "[\"█░\", \"░█\"]".to_string()));
+    }
+
+    #[test]
+    fn renders_results_and_errors() {
+        assert_eq!(render(&[]), "{\n  \"days\": []\n}");
+
+        let solved = Solved {
+            part_one: Answer::from(1),
+            part_two: Answer::Text("a\"b".to_string()),
+            timings: Timings {
+                parse: Duration::from_nanos(1),
+                part_one: Duration::from_nanos(2),
+                part_two: Duration::from_nanos(3),
+            },
+        };
+        let results = [
+            (1, "input".to_string(), Ok(solved)),
+            (
+                7,
+                "example".to_string(),
+                Err(Error::no_solution(7, 2, "no \"/\"")),
+            ),
+        ];
+
+        assert_eq!(
+            render(&results),
+            "{\n  \"days\": [\n    \
+             {\"day\": 1, \"input\": \"input\", \"parse_ns\": 1, \"total_ns\": 6, \"parts\": [\
+             {\"part\": 1, \"type\": \"number\", \"answer\": 1, \"time_ns\": 2}, \
+             {\"part\": 2, \"type\": \"string\", \"answer\": \"a\\\"b\", \"time_ns\": 3}]},\n    \
+             {\"day\": 7, \"input\": \"example\", \"error\": \"day 7, part 2: no \\\"/\\\"\"}\n  ]\n}"
+        );
+    }
+}
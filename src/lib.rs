@@ -0,0 +1,94 @@
+pub mod answers;
+pub mod day_1;
+pub mod day_10;
+pub mod day_11;
+pub mod day_12;
+pub mod day_2;
+pub mod day_3;
+pub mod day_4;
+pub mod day_5;
+pub mod day_6;
+pub mod day_7;
+pub mod day_8;
+pub mod day_9;
+pub mod error;
+pub mod fetch;
+pub mod grid;
+pub mod input;
+pub mod parse;
+pub mod point;
+pub mod solution;
+
+use std::path::Path;
+
+use error::Result;
+use input::Issue;
+use solution::{solve, solve_parallel, Solution, Solved};
+
+/// Parses an input and returns the answers to both parts of a day, along with their timings.
+pub type Runner = fn(&str) -> Result<Solved>;
+
+/// A registered day and the ways to run it.
+pub struct Day {
+    pub number: u8,
+    pub solve: Runner,
+    /// Runs both parts at the same time.
+    pub solve_parallel: Runner,
+    pub keeps_trailing_whitespace: bool,
+    /// The parts only read what parsing found, so their timings say little.
+    pub solves_while_parsing: bool,
+}
+
+impl Day {
+    /// Every issue found in an input of this day.
+    pub fn diagnose(&self, input: &str) -> Vec<Issue> {
+        self.relevant(input::diagnose(input))
+    }
+
+    /// Every issue found in an input file of this day, including it not being there.
+    pub fn diagnose_path(&self, path: &Path) -> Vec<Issue> {
+        self.relevant(input::diagnose_path(path))
+    }
+
+    fn relevant(&self, issues: Vec<Issue>) -> Vec<Issue> {
+        issues
+            .into_iter()
+            .filter(|issue| {
+                !(self.keeps_trailing_whitespace
+                    && matches!(issue, Issue::TrailingWhitespace { .. }))
+            })
+            .collect()
+    }
+}
+
+macro_rules! day {
+    ($number:literal, $solution:ty) => {
+        Day {
+            number: $number,
+            solve: solve::<$solution>,
+            solve_parallel: solve_parallel::<$solution>,
+            keeps_trailing_whitespace: <$solution as Solution>::KEEPS_TRAILING_WHITESPACE,
+            solves_while_parsing: <$solution as Solution>::SOLVES_WHILE_PARSING,
+        }
+    };
+}
+
+/// Every implemented day, in order.
+pub const DAYS: [Day; 12] = [
+    day!(1, day_1::Day1),
+    day!(2, day_2::Day2),
+    day!(3, day_3::Day3),
+    day!(4, day_4::Day4),
+    day!(5, day_5::Day5),
+    day!(6, day_6::Day6),
+    day!(7, day_7::Day7),
+    day!(8, day_8::Day8),
+    day!(9, day_9::Day9),
+    day!(10, day_10::Day10),
+    day!(11, day_11::Day11),
+    day!(12, day_12::Day12),
+];
+
+pub fn find_day(number: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|day| day.number == number)
+}
@@ -1,38 +1,696 @@
-mod day_1;
-mod day_10;
-mod day_11;
-mod day_12;
-mod day_2;
-mod day_3;
-mod day_4;
-mod day_5;
-mod day_6;
-mod day_7;
-mod day_8;
-mod day_9;
-
-use std::fs;
-
-fn read_data(day: u8) -> String {
-    let f = format!("data/day_{}.txt", day);
-    fs::read_to_string(f).unwrap()
-}
-
-fn run_normal() {
-    // day_1::run_1(read_data(1));
-    // day_2::run(read_data(2));
-    // day_3::run(read_data(3));
-    // day_4::run(read_data(4));
-    // day_5::run(read_data(5));
-    // day_6::run(read_data(6));
-    // day_7::run(read_data(7));
-    // day_8::run(read_data(8));
-    // day_9::run(read_data(9));
-    // day_10::run(read_data(10));
-    // day_11::run(read_data(11));
-    day_12::run(read_data(12));
+mod bench;
+mod cli;
+mod json;
+mod scaffold;
+mod watch;
+
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    process, thread,
+    time::Duration,
+};
+
+use advent_of_code_22::error::{Error, Result};
+
+use advent_of_code_22::{
+    answers::{Answers, Check},
+    day_1::{Inventory, Mode},
+    day_2::{self, Decoder, Rules},
+    fetch::{Fetched, Fetcher},
+    find_day,
+    input::{self, Source},
+    solution::{Answer, Solved, Timings},
+    Day, DAYS,
+};
+use cli::{Command, Letters};
+use watch::{Change, Stamps};
+
+/// The real input of a day, or all of its examples.
+fn sources(day: u8, example: bool) -> Vec<Source> {
+    if example {
+        let examples = input::examples(day);
+        if examples.is_empty() {
+            eprintln!("Day {}: no examples found", day);
+        }
+        examples
+    } else {
+        vec![Source::Real]
+    }
+}
+
+/// Warns about the issues found in an input, failing on the ones that make it unsolvable.
+fn checked(day: &Day, label: &str, input: Result<String>) -> Result<String> {
+    let input = input?;
+
+    for issue in day.diagnose(&input) {
+        if issue.is_fatal() {
+            return Err(Error::Input {
+                day: day.number,
+                issue,
+            });
+        }
+
+        eprintln!("Day {} ({}): warning: {}", day.number, label, issue);
+    }
+
+    Ok(input)
+}
+
+/// Every input to run for a day, along with the label shown for it.
+fn inputs(day: &Day, example: bool, path: Option<&Path>) -> Vec<(String, Result<String>)> {
+    let inputs = match path {
+        Some(path) => {
+            let label = if path == Path::new("-") {
+                "stdin".to_string()
+            } else {
+                path.display().to_string()
+            };
+            vec![(label, input::read_path(path))]
+        }
+        None => sources(day.number, example)
+            .into_iter()
+            .map(|source| (source.to_string(), input::read(day.number, source)))
+            .collect::<Vec<_>>(),
+    };
+
+    inputs
+        .into_iter()
+        .map(|(label, input)| {
+            let input = checked(day, &label, input);
+            (label, input)
+        })
+        .collect()
+}
+
+fn print_header(day: u8, label: &str) {
+    if label == Source::Real.to_string() {
+        println!("Day {}:", day);
+    } else {
+        println!("Day {} ({}):", day, label);
+    }
+}
+
+fn print_answer(part: u8, answer: &Answer) {
+    match answer {
+        Answer::Image(_) => println!("  Part {}:\n{}", part, answer),
+        answer => println!("  Part {}: {}", part, answer),
+    }
+}
+
+fn print_timings(timings: &Timings) {
+    println!(
+        "  Time: {:.2?} (parse {:.2?}, part 1 {:.2?}, part 2 {:.2?})",
+        timings.total(),
+        timings.parse,
+        timings.part_one,
+        timings.part_two
+    );
+}
+
+/// Solves every input of the given days one after the other, in order.
+fn solve_days(
+    days: &[u8],
+    example: bool,
+    path: Option<&Path>,
+) -> Vec<(u8, String, Result<Solved>)> {
+    let mut results = Vec::new();
+
+    for day in days.iter().filter_map(|day| find_day(*day)) {
+        for (label, input) in inputs(day, example, path) {
+            let result = input.and_then(|input| (day.solve)(&input));
+            results.push((day.number, label, result));
+        }
+    }
+
+    results
+}
+
+/// Solves every input of the given days on its own thread, running both parts of each at once.
+/// The results keep the order of the days.
+fn solve_days_parallel(
+    days: &[u8],
+    example: bool,
+    path: Option<&Path>,
+) -> Vec<(u8, String, Result<Solved>)> {
+    thread::scope(|scope| {
+        let handles = days
+            .iter()
+            .filter_map(|day| find_day(*day))
+            .flat_map(|day| {
+                inputs(day, example, path)
+                    .into_iter()
+                    .map(move |(label, input)| {
+                        let handle = scope
+                            .spawn(move || input.and_then(|input| (day.solve_parallel)(&input)));
+                        (day.number, label, handle)
+                    })
+            })
+            .collect::<Vec<_>>();
+
+        handles
+            .into_iter()
+            .map(|(day, label, handle)| {
+                let result = handle.join().expect("day panicked");
+                (day, label, result)
+            })
+            .collect()
+    })
+}
+
+/// Runs every given day, returning whether all of them succeeded.
+fn run_days(
+    days: &[u8],
+    time: bool,
+    example: bool,
+    as_json: bool,
+    parallel: bool,
+    path: Option<&Path>,
+) -> bool {
+    let results = if parallel {
+        solve_days_parallel(days, example, path)
+    } else {
+        solve_days(days, example, path)
+    };
+
+    let solved_days = results.iter().map(|(day, _, _)| *day).collect::<Vec<_>>();
+    let success = days.iter().all(|day| solved_days.contains(day))
+        && results.iter().all(|(_, _, result)| result.is_ok());
+
+    if as_json {
+        println!("{}", json::render(&results));
+        return success;
+    }
+
+    for (day, label, result) in &results {
+        print_header(*day, label);
+
+        match result {
+            Ok(solved) => {
+                print_answer(1, &solved.part_one);
+                print_answer(2, &solved.part_two);
+
+                if time {
+                    print_timings(&solved.timings);
+
+                    if find_day(*day).is_some_and(|day| day.solves_while_parsing) {
+                        println!("  Both parts are solved while parsing.");
+                    }
+                }
+            }
+            Err(e) => eprintln!("  error: {}", e),
+        }
+    }
+
+    success
+}
+
+/// Benchmarks every given day, returning whether all of them succeeded.
+fn bench_days(days: &[u8], runs: usize, path: Option<&Path>) -> bool {
+    let mut success = true;
+    let mut results = Vec::new();
+
+    for day in days.iter().filter_map(|day| find_day(*day)) {
+        for (_, input) in inputs(day, false, path) {
+            match input.and_then(|input| bench::bench(day.solve, &input, runs)) {
+                Ok(stats) => results.push((day.number, stats)),
+                Err(e) => {
+                    eprintln!("Day {}: error: {}", day.number, e);
+                    success = false;
+                }
+            }
+        }
+    }
+
+    bench::print_table(&results, runs);
+
+    success
+}
+
+fn print_check(part: u8, check: &Check) {
+    match check {
+        Check::Pass => println!("  Part {}: pass", part),
+        Check::Missing => println!("  Part {}: missing", part),
+        Check::Fail { expected, actual } => {
+            println!("  Part {}: FAIL", part);
+            println!("    expected: {}", expected);
+            println!("    actual:   {}", actual);
+        }
+    }
+}
+
+/// Checks every given day against the known answers, returning whether none failed.
+fn verify_days(days: &[u8], example: bool) -> bool {
+    let answers = match Answers::load(&input::data_dir().join("answers.toml")) {
+        Ok(answers) => answers,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            return false;
+        }
+    };
+
+    let mut success = true;
+    let (mut passed, mut failed, mut missing) = (0, 0, 0);
+
+    for day in days.iter().filter_map(|day| find_day(*day)) {
+        let sources = sources(day.number, example);
+        success &= !sources.is_empty();
+
+        for source in sources {
+            let label = source.to_string();
+            print_header(day.number, &label);
+
+            let input = checked(day, &label, input::read(day.number, source));
+            let solved = match input.and_then(|input| (day.solve)(&input)) {
+                Ok(solved) => solved,
+                Err(e) => {
+                    eprintln!("  error: {}", e);
+                    success = false;
+                    continue;
+                }
+            };
+
+            let expected = answers.get(day.number, source).cloned().unwrap_or_default();
+            let checks = [
+                Check::new(expected.part_one.as_ref(), &solved.part_one),
+                Check::new(expected.part_two.as_ref(), &solved.part_two),
+            ];
+
+            for (part, check) in (1..).zip(&checks) {
+                print_check(part, check);
+
+                match check {
+                    Check::Pass => passed += 1,
+                    Check::Fail { .. } => failed += 1,
+                    Check::Missing => missing += 1,
+                }
+            }
+        }
+    }
+
+    println!("{} passed, {} failed, {} missing", passed, failed, missing);
+
+    success && failed == 0
+}
+
+/// Files whose changes trigger a new run of the given days.
+fn watched_files(days: &[u8], example: bool) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+
+    for day in days {
+        if example {
+            files.extend(
+                input::examples(*day)
+                    .into_iter()
+                    .map(|source| input::path(*day, source)),
+            );
+        } else {
+            files.push(input::path(*day, Source::Real));
+        }
+
+        files.push(watch::source_file(*day));
+    }
+
+    files
+}
+
+/// Re-runs the given days whenever their inputs change, comparing the answers with the previous
+/// run. A change to the source of a day rebuilds the runner and restarts it on the new code. Only
+/// stops when interrupted.
+fn watch_days(days: &[u8], example: bool, interval: Duration) -> ! {
+    // Looked up before any rebuild replaces the file of the running binary
+    let exe = std::env::current_exe();
+    let mut stamps = Stamps::default();
+    let mut previous = HashMap::new();
+    let mut first_run = true;
+
+    println!("Watching for changes, press Ctrl-C to stop.");
+
+    loop {
+        let current = Stamps::of(watched_files(days, example));
+        let changed = current.changed_since(&stamps);
+
+        if !changed.is_empty() && !first_run {
+            println!();
+            for path in &changed {
+                println!("Changed: {}", path.display());
+            }
+        }
+
+        let sources = days
+            .iter()
+            .map(|day| watch::source_file(*day))
+            .collect::<Vec<_>>();
+        if !first_run && changed.iter().any(|path| sources.iter().any(|s| s == path)) {
+            println!("Rebuilding...");
+
+            match &exe {
+                Ok(exe) if watch::rebuild() => {
+                    println!("Restarting with the new code.");
+                    eprintln!("error: could not restart: {}", watch::restart(exe));
+                }
+                Ok(_) => println!("The build failed, still running the previous code."),
+                Err(e) => eprintln!("error: could not find the running binary: {}", e),
+            }
+        }
+
+        if first_run
+            || changed
+                .iter()
+                .any(|path| !sources.iter().any(|s| s == path))
+        {
+            for (day, label, result) in solve_days(days, example, None) {
+                print_header(day, &label);
+
+                match result {
+                    Ok(solved) => {
+                        for (part, answer) in [(1, solved.part_one), (2, solved.part_two)] {
+                            let key = (day, label.clone(), part);
+                            watch::print_change(
+                                part,
+                                &answer,
+                                &Change::new(previous.get(&key), &answer),
+                            );
+                            previous.insert(key, answer);
+                        }
+                    }
+                    Err(e) => eprintln!("  error: {}", e),
+                }
+            }
+        }
+
+        stamps = current;
+        first_run = false;
+        thread::sleep(interval);
+    }
+}
+
+/// Downloads the inputs of the given days into the data folder, returning whether all of them
+/// are there afterwards.
+fn fetch_days(days: &[u8], url: Option<&str>, session: Option<&str>) -> bool {
+    let fetcher = match Fetcher::from_env(url, session) {
+        Ok(fetcher) => fetcher,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            return false;
+        }
+    };
+
+    let mut success = true;
+    let dir = input::data_dir();
+
+    for day in days {
+        match fetcher.download(*day, &dir) {
+            Ok(Fetched::Downloaded(path)) => println!("Day {}: saved {}", day, path.display()),
+            Ok(Fetched::Cached(path)) => {
+                println!("Day {}: {} already exists", day, path.display())
+            }
+            Err(e) => {
+                eprintln!("error: {}", e);
+                success = false;
+            }
+        }
+    }
+
+    success
+}
+
+/// Reports the issues of the inputs of the given days, returning whether all of them can be solved.
+fn check_days(days: &[u8], example: bool) -> bool {
+    let mut success = true;
+
+    for day in days.iter().filter_map(|day| find_day(*day)) {
+        let sources = sources(day.number, example);
+        success &= !sources.is_empty();
+
+        for source in sources {
+            let issues = day.diagnose_path(&input::path(day.number, source));
+            let label = format!("Day {} ({})", day.number, source);
+
+            if issues.is_empty() {
+                println!("{}: ok", label);
+            }
+
+            for issue in issues {
+                if issue.is_fatal() {
+                    println!("{}: error: {}", label, issue);
+                    success = false;
+                } else {
+                    println!("{}: warning: {}", label, issue);
+                }
+            }
+        }
+    }
+
+    success
+}
+
+fn print_inventory(inventory: &Inventory, top: usize) {
+    println!("  Elves: {}", inventory.len());
+
+    let ranked = inventory
+        .top(top)
+        .into_iter()
+        .map(|(elf, total)| format!("elf {} ({})", elf, total))
+        .collect::<Vec<_>>();
+    if !ranked.is_empty() {
+        println!("  Top {}: {}", ranked.len(), ranked.join(", "));
+    }
+
+    if let (Some(mean), Some(median)) = (inventory.mean(), inventory.median()) {
+        println!("  Mean: {:.1}, median: {:.1}", mean, median);
+    }
+
+    let percentiles = [25.0, 75.0, 90.0]
+        .into_iter()
+        .filter_map(|percent| {
+            let total = inventory.percentile(percent)?;
+            Some(format!("{}th {}", percent, total))
+        })
+        .collect::<Vec<_>>();
+    if !percentiles.is_empty() {
+        println!("  Percentiles: {}", percentiles.join(", "));
+    }
+
+    for (total, elves) in inventory.ties() {
+        let elves = elves.iter().map(|elf| elf.to_string()).collect::<Vec<_>>();
+        println!("  Tie: elves {} carry {}", elves.join(", "), total);
+    }
+}
+
+/// Ranks the day 1 elves of every input and describes what they carry, listing the lines skipped
+/// in lenient mode. Returns whether every input could be read.
+fn describe_elves(example: bool, path: Option<&Path>, top: usize, mode: Mode) -> bool {
+    let Some(day) = find_day(1) else {
+        eprintln!("error: day 1 is not implemented");
+        return false;
+    };
+
+    let mut success = true;
+
+    for (label, input) in inputs(day, example, path) {
+        print_header(day.number, &label);
+
+        match input.and_then(|input| Inventory::from_reader(input.as_bytes(), mode)) {
+            Ok((inventory, skipped)) => {
+                print_inventory(&inventory, top);
+                for skipped in skipped {
+                    println!("  Skipped {}", skipped);
+                }
+            }
+            Err(e) => {
+                eprintln!("  error: {}", e);
+                success = false;
+            }
+        }
+    }
+
+    success
+}
+
+/// The elves' lettering of the guide, with the mappings given on the command line replacing
+/// their part of it.
+fn decoder(rules: &Rules, letters: &Letters) -> std::result::Result<Decoder, String> {
+    let mut decoder = Decoder::lettered(rules);
+
+    if let Some(mapping) = &letters.opponents {
+        decoder = decoder
+            .with_opponents(rules, mapping)
+            .map_err(|e| format!("--opponents: {}", e))?;
+    }
+    if let Some(mapping) = &letters.responses {
+        decoder = decoder
+            .with_responses(rules, mapping)
+            .map_err(|e| format!("--responses: {}", e))?;
+    }
+    if let Some(mapping) = &letters.states {
+        decoder = decoder
+            .with_states(mapping)
+            .map_err(|e| format!("--states: {}", e))?;
+    }
+
+    Ok(decoder)
+}
+
+/// Scores the day 2 guide under every way of giving the letters of its second column to the
+/// shapes to play, and once more reading them as outcomes. Returns whether every input scored.
+fn score_guides(
+    example: bool,
+    path: Option<&Path>,
+    rules: Option<&Path>,
+    letters: &Letters,
+) -> bool {
+    let Some(day) = find_day(2) else {
+        eprintln!("error: day 2 is not implemented");
+        return false;
+    };
+
+    let rules = match rules
+        .map(Rules::load)
+        .unwrap_or_else(|| Ok(Rules::classic()))
+    {
+        Ok(rules) => rules,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            return false;
+        }
+    };
+    let decoder = match decoder(&rules, letters) {
+        Ok(decoder) => decoder,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            return false;
+        }
+    };
+
+    let mut success = true;
+
+    for (label, input) in inputs(day, example, path) {
+        print_header(day.number, &label);
+
+        let scored = input
+            .and_then(|input| day_2::get_games(&decoder, &input))
+            .and_then(|games| {
+                let shapes = day_2::score_shapes(&rules, &decoder, &games)?;
+                let states = day_2::score_states(&rules, &decoder, &games)?;
+                let permutations = day_2::score_permutations(&rules, &decoder, &games)?;
+                Ok((shapes, states, permutations))
+            });
+
+        match scored {
+            Ok((shapes, states, permutations)) => {
+                println!(
+                    "  As shapes: {} (player A: {})",
+                    shapes.player_b, shapes.player_a
+                );
+                println!("  As outcomes: {}", states);
+                println!("  Every lettering of the shapes:");
+                for (mapping, score) in permutations {
+                    println!("    {}: {}", mapping, score);
+                }
+            }
+            Err(e) => {
+                eprintln!("  error: {}", e);
+                success = false;
+            }
+        }
+    }
+
+    success
+}
+
+fn list_days() {
+    println!("Implemented days:");
+    for day in DAYS.iter() {
+        println!("  Day {}", day.number);
+    }
 }
 
 fn main() {
-    run_normal();
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    let available = DAYS.iter().map(|day| day.number).collect::<Vec<_>>();
+
+    match cli::parse(&args, &available) {
+        Ok(Command::Run {
+            days,
+            time,
+            example,
+            json,
+            parallel,
+            input,
+        }) => {
+            if !run_days(&days, time, example, json, parallel, input.as_deref()) {
+                process::exit(1);
+            }
+        }
+        Ok(Command::Bench { days, runs, input }) => {
+            if !bench_days(&days, runs, input.as_deref()) {
+                process::exit(1);
+            }
+        }
+        Ok(Command::Verify { days, example }) => {
+            if !verify_days(&days, example) {
+                process::exit(1);
+            }
+        }
+        Ok(Command::Watch {
+            days,
+            example,
+            interval,
+        }) => watch_days(&days, example, interval),
+        Ok(Command::Fetch { days, url, session }) => {
+            if !fetch_days(&days, url.as_deref(), session.as_deref()) {
+                process::exit(1);
+            }
+        }
+        Ok(Command::Check { days, example }) => {
+            if !check_days(&days, example) {
+                process::exit(1);
+            }
+        }
+        Ok(Command::Elves {
+            example,
+            input,
+            top,
+            lenient,
+        }) => {
+            let mode = if lenient { Mode::Lenient } else { Mode::Strict };
+            if !describe_elves(example, input.as_deref(), top, mode) {
+                process::exit(1);
+            }
+        }
+        Ok(Command::Guide {
+            example,
+            input,
+            rules,
+            letters,
+        }) => {
+            if !score_guides(example, input.as_deref(), rules.as_deref(), &letters) {
+                process::exit(1);
+            }
+        }
+        Ok(Command::NewDay(day)) => {
+            match scaffold::new_day(Path::new(env!("CARGO_MANIFEST_DIR")), day) {
+                Ok(paths) => {
+                    for path in paths {
+                        println!("Wrote {}", path.display());
+                    }
+                    println!(
+                        "Add the example to data/day_{}.example.txt and rebuild.",
+                        day
+                    );
+                }
+                Err(e) => {
+                    eprintln!("error: {}", e);
+                    process::exit(1);
+                }
+            }
+        }
+        Ok(Command::List) => list_days(),
+        Ok(Command::Help) => println!("{}", cli::USAGE),
+        Err(e) => {
+            eprintln!("error: {}\n\n{}", e, cli::USAGE);
+            process::exit(2);
+        }
+    }
 }
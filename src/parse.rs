@@ -0,0 +1,349 @@
+//! Small building blocks for reading puzzle inputs. Line parsers return a [`LineError`] pointing
+//! at the 1-based column that failed, which the caller places in the input with [`LineError::at`].
+
+use std::str::FromStr;
+
+use crate::error::{LineError, Result};
+
+/// Reads a line from left to right, keeping track of the column it stopped at.
+#[derive(Debug, Clone)]
+pub struct Cursor<'a> {
+    line: &'a str,
+    offset: usize,
+}
+
+impl<'a> Cursor<'a> {
+    pub fn new(line: &'a str) -> Self {
+        Self { line, offset: 0 }
+    }
+
+    /// 1-based column of the next character.
+    pub fn column(&self) -> usize {
+        self.line[..self.offset].chars().count() + 1
+    }
+
+    /// What is left to read.
+    pub fn rest(&self) -> &'a str {
+        &self.line[self.offset..]
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.rest().is_empty()
+    }
+
+    /// An error pointing at the next character.
+    pub fn error(&self, reason: impl Into<String>) -> LineError {
+        self.error_at(self.column(), reason)
+    }
+
+    /// An error pointing at a column read earlier.
+    pub fn error_at(&self, column: usize, reason: impl Into<String>) -> LineError {
+        LineError::new(column, self.line, reason)
+    }
+
+    /// Consumes the characters matching `predicate`, possibly none.
+    pub fn take_while(&mut self, mut predicate: impl FnMut(char) -> bool) -> &'a str {
+        let rest = self.rest();
+        let length = rest.find(|c| !predicate(c)).unwrap_or(rest.len());
+        self.offset += length;
+        &rest[..length]
+    }
+
+    /// Consumes the next character, if any.
+    pub fn char(&mut self) -> Option<char> {
+        let next = self.rest().chars().next()?;
+        self.offset += next.len_utf8();
+        Some(next)
+    }
+
+    pub fn spaces(&mut self) {
+        self.take_while(char::is_whitespace);
+    }
+
+    /// Consumes `tag` if the line continues with it.
+    pub fn eat(&mut self, tag: &str) -> bool {
+        let found = self.rest().starts_with(tag);
+        if found {
+            self.offset += tag.len();
+        }
+        found
+    }
+
+    /// Consumes `tag`, failing if the line continues with anything else.
+    pub fn tag(&mut self, tag: &str) -> std::result::Result<(), LineError> {
+        if self.eat(tag) {
+            Ok(())
+        } else {
+            Err(self.error(format!("expected `{}`", tag)))
+        }
+    }
+
+    /// A run of characters up to the next whitespace.
+    pub fn word(&mut self) -> std::result::Result<&'a str, LineError> {
+        let column = self.column();
+        match self.take_while(|c| !c.is_whitespace()) {
+            "" => Err(self.error_at(column, "expected a word")),
+            word => Ok(word),
+        }
+    }
+
+    /// A number made of digits only.
+    pub fn uint<T: FromStr>(&mut self) -> std::result::Result<T, LineError> {
+        let column = self.column();
+        match self.take_while(|c| c.is_ascii_digit()) {
+            "" => Err(self.error_at(column, "expected a number")),
+            digits => digits
+                .parse()
+                .map_err(|_| self.error_at(column, "number too large")),
+        }
+    }
+
+    /// A number with an optional `-` or `+` sign.
+    pub fn int<T: FromStr>(&mut self) -> std::result::Result<T, LineError> {
+        let (start, column) = (self.offset, self.column());
+        let _ = self.eat("-") || self.eat("+");
+
+        if self.take_while(|c| c.is_ascii_digit()).is_empty() {
+            self.offset = start;
+            return Err(self.error("expected a number"));
+        }
+
+        self.line[start..self.offset]
+            .parse()
+            .map_err(|_| self.error_at(column, "number too large"))
+    }
+
+    /// One or more items read by `item`, separated by `separator`.
+    pub fn list<T>(
+        &mut self,
+        separator: &str,
+        mut item: impl FnMut(&mut Self) -> std::result::Result<T, LineError>,
+    ) -> std::result::Result<Vec<T>, LineError> {
+        let mut items = vec![item(self)?];
+        while self.eat(separator) {
+            items.push(item(self)?);
+        }
+        Ok(items)
+    }
+
+    /// The `key:` part of a `key: value` line, along with the indentation and spaces around it.
+    pub fn key(&mut self, key: &str) -> std::result::Result<(), LineError> {
+        self.spaces();
+        self.tag(key)?;
+        self.tag(":")?;
+        self.spaces();
+        Ok(())
+    }
+
+    /// Fails unless the whole line has been read.
+    pub fn end(&self) -> std::result::Result<(), LineError> {
+        match self.rest() {
+            "" => Ok(()),
+            rest => Err(self.error(format!("unexpected `{}`", rest))),
+        }
+    }
+}
+
+/// Reads the whole of `line` with `parse`, failing on anything left over.
+pub fn line<'a, T>(
+    line: &'a str,
+    parse: impl FnOnce(&mut Cursor<'a>) -> std::result::Result<T, LineError>,
+) -> std::result::Result<T, LineError> {
+    let mut cursor = Cursor::new(line);
+    let value = parse(&mut cursor)?;
+    cursor.end()?;
+    Ok(value)
+}
+
+/// Parses every line of the input, placing the first error at its line.
+pub fn each_line<'a, T>(
+    day: u8,
+    input: &'a str,
+    mut parse: impl FnMut(&'a str) -> std::result::Result<T, LineError>,
+) -> Result<Vec<T>> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(index, line)| parse(line).map_err(|e| e.at(day, index + 1)))
+        .collect()
+}
+
+/// Lines of the input between blank lines, without any trailing `\r`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Block<'a> {
+    /// 1-based line of the input where the block starts.
+    pub first_line: usize,
+    pub lines: Vec<&'a str>,
+}
+
+impl<'a> Block<'a> {
+    /// The lines of the block along with their 1-based line in the input.
+    pub fn numbered(&self) -> impl Iterator<Item = (usize, &'a str)> + '_ {
+        (self.first_line..).zip(self.lines.iter().copied())
+    }
+}
+
+/// Groups of lines separated by blank lines, see [`blocks`].
+#[derive(Debug, Clone)]
+pub struct Blocks<'a> {
+    lines: std::iter::Enumerate<std::str::Lines<'a>>,
+}
+
+impl<'a> Iterator for Blocks<'a> {
+    type Item = Block<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (index, first) = self.lines.find(|(_, line)| !is_blank(line))?;
+        let mut block = Block {
+            first_line: index + 1,
+            lines: vec![first.trim_end_matches('\r')],
+        };
+
+        for (_, line) in self.lines.by_ref() {
+            if is_blank(line) {
+                break;
+            }
+            block.lines.push(line.trim_end_matches('\r'));
+        }
+
+        Some(block)
+    }
+}
+
+// Lines with nothing but whitespace, like a stray `\r`, still separate blocks
+fn is_blank(line: &str) -> bool {
+    line.trim().is_empty()
+}
+
+/// Splits the input at blank lines, whatever the size of each block. Runs of blank lines,
+/// including leading and trailing ones, never produce empty blocks.
+pub fn blocks(input: &str) -> Blocks<'_> {
+    Blocks {
+        lines: input.lines().enumerate(),
+    }
+}
+
+/// Splits a line by whitespace, keeping the 1-based column where each word starts. Columns count
+/// characters, like [`Cursor::column`].
+pub fn words(line: &str) -> Vec<(usize, &str)> {
+    let mut words = Vec::new();
+    let mut start = None;
+
+    let chars = line.char_indices().chain([(line.len(), ' ')]);
+    for (column, (index, c)) in (1..).zip(chars) {
+        match (c.is_whitespace(), start) {
+            (false, None) => start = Some((column, index)),
+            (true, Some((column, from))) => {
+                words.push((column, &line[from..index]));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+
+    words
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_numbers() {
+        let mut cursor = Cursor::new("12 -3 +4 x");
+        assert_eq!(cursor.uint::<u8>(), Ok(12));
+        cursor.spaces();
+        assert_eq!(cursor.int::<i32>(), Ok(-3));
+        cursor.spaces();
+        assert_eq!(cursor.int::<i32>(), Ok(4));
+        cursor.spaces();
+        assert_eq!(cursor.int::<i32>().unwrap_err().column, 10);
+        assert_eq!(cursor.column(), 10);
+
+        assert_eq!(Cursor::new("-1").uint::<u8>().unwrap_err().column, 1);
+
+        let mut cursor = Cursor::new("é!");
+        assert_eq!(
+            (cursor.char(), cursor.char(), cursor.char()),
+            (Some('é'), Some('!'), None)
+        );
+        assert_eq!(cursor.column(), 3);
+        let lengths = Cursor::new("a 300").list(" ", |c| c.word().map(str::len));
+        assert_eq!(lengths, Ok(vec![1, 3]));
+        let error = line("1 300", |c| c.list(" ", |c| c.uint::<u8>()));
+        assert_eq!(error.unwrap_err().reason, "number too large");
+    }
+
+    #[test]
+    fn reads_key_value_lists() {
+        let items = line("  Items: 1, 2, 3", |c| {
+            c.key("Items")?;
+            c.list(", ", |c| c.uint::<u32>())
+        });
+        assert_eq!(items, Ok(vec![1, 2, 3]));
+
+        let error = line("  Items: 1, x", |c| {
+            c.key("Items")?;
+            c.list(", ", |c| c.uint::<u32>())
+        });
+        assert_eq!(error.unwrap_err().column, 13);
+
+        let error = line("Item: 1", |c| c.key("Items")).unwrap_err();
+        assert_eq!(
+            (error.column, error.reason.as_str()),
+            (1, "expected `Items`")
+        );
+    }
+
+    #[test]
+    fn rejects_leftovers() {
+        let error = line("move 1 up", |c| {
+            c.tag("move ")?;
+            c.uint::<u8>()
+        });
+        assert_eq!(
+            error.unwrap_err().to_string(),
+            "column 7: unexpected ` up` in `move 1 up`"
+        );
+    }
+
+    #[test]
+    fn places_errors_at_their_line() {
+        let parsed = each_line(1, "1\n2", |l| line(l, |c| c.uint::<u8>()));
+        assert_eq!(parsed.unwrap(), vec![1, 2]);
+
+        let error = each_line(1, "1\nx", |l| line(l, |c| c.uint::<u8>())).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "day 1, line 2, column 1: expected a number in `x`"
+        );
+    }
+
+    #[test]
+    fn splits_blocks_at_empty_lines() {
+        let blocks = blocks("a\nb\n\nc\n\n\nd\n").collect::<Vec<_>>();
+        assert_eq!(blocks.len(), 3);
+        assert_eq!(blocks[0].lines, ["a", "b"]);
+        assert_eq!(blocks[1].numbered().collect::<Vec<_>>(), [(4, "c")]);
+        assert_eq!(blocks[2].first_line, 7);
+    }
+
+    #[test]
+    fn splits_blocks_of_any_size_and_line_ending() {
+        let blocks = blocks("\r\na\r\nb\r\n\r\nc\r\n \t\nd\ne\nf\r\n\n\n").collect::<Vec<_>>();
+        let lines = blocks.iter().map(|b| b.lines.clone()).collect::<Vec<_>>();
+        assert_eq!(lines, vec![vec!["a", "b"], vec!["c"], vec!["d", "e", "f"]]);
+        assert_eq!(blocks[0].first_line, 2);
+        assert_eq!(blocks[2].first_line, 7);
+
+        assert_eq!(super::blocks("a\r").next().unwrap().lines, ["a"]);
+        assert_eq!(super::blocks("\n \n\n").count(), 0);
+    }
+
+    #[test]
+    fn splits_words() {
+        assert_eq!(words("  ab c"), vec![(3, "ab"), (6, "c")]);
+        assert_eq!(words("é ü x"), vec![(1, "é"), (3, "ü"), (5, "x")]);
+        assert!(words("   ").is_empty());
+    }
+}
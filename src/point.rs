@@ -0,0 +1,223 @@
+use std::{
+    fmt,
+    ops::{Add, AddAssign, Mul, Neg, Sub},
+};
+
+/// A position on the plane, `x` growing to the right and `y` downwards like the rows of a map.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+/// The difference between two points.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Vector {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Point {
+    pub const ORIGIN: Self = Self::new(0, 0);
+
+    pub const fn new(x: i64, y: i64) -> Self {
+        Self { x, y }
+    }
+
+    /// Distance walking only along the axes.
+    pub fn manhattan(self, other: Self) -> i64 {
+        (other - self).manhattan()
+    }
+
+    /// Distance when diagonal steps are allowed, like a king on a chessboard.
+    pub fn chebyshev(self, other: Self) -> i64 {
+        (other - self).chebyshev()
+    }
+
+    /// Moves at most one step on each axis towards `target`.
+    pub fn step_towards(self, target: Self) -> Self {
+        self + (target - self).signum()
+    }
+}
+
+impl Vector {
+    pub const ZERO: Self = Self::new(0, 0);
+    pub const UP: Self = Self::new(0, -1);
+    pub const DOWN: Self = Self::new(0, 1);
+    pub const LEFT: Self = Self::new(-1, 0);
+    pub const RIGHT: Self = Self::new(1, 0);
+
+    /// Up, down, left and right.
+    pub const ORTHOGONAL: [Self; 4] = [Self::UP, Self::DOWN, Self::LEFT, Self::RIGHT];
+
+    /// The orthogonal steps followed by the diagonal ones.
+    pub const ALL_DIRECTIONS: [Self; 8] = [
+        Self::UP,
+        Self::DOWN,
+        Self::LEFT,
+        Self::RIGHT,
+        Self::new(-1, -1),
+        Self::new(1, -1),
+        Self::new(-1, 1),
+        Self::new(1, 1),
+    ];
+
+    pub const fn new(x: i64, y: i64) -> Self {
+        Self { x, y }
+    }
+
+    /// The sign of each component, turning any vector into a single step.
+    pub fn signum(self) -> Self {
+        Self::new(self.x.signum(), self.y.signum())
+    }
+
+    pub fn manhattan(self) -> i64 {
+        self.x.abs() + self.y.abs()
+    }
+
+    pub fn chebyshev(self) -> i64 {
+        self.x.abs().max(self.y.abs())
+    }
+}
+
+impl Add<Vector> for Point {
+    type Output = Point;
+
+    fn add(self, vector: Vector) -> Point {
+        Point::new(self.x + vector.x, self.y + vector.y)
+    }
+}
+
+impl AddAssign<Vector> for Point {
+    fn add_assign(&mut self, vector: Vector) {
+        *self = *self + vector;
+    }
+}
+
+impl Sub<Vector> for Point {
+    type Output = Point;
+
+    fn sub(self, vector: Vector) -> Point {
+        self + -vector
+    }
+}
+
+impl Sub for Point {
+    type Output = Vector;
+
+    fn sub(self, other: Point) -> Vector {
+        Vector::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl Add for Vector {
+    type Output = Vector;
+
+    fn add(self, other: Vector) -> Vector {
+        Vector::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl Mul<i64> for Vector {
+    type Output = Vector;
+
+    fn mul(self, times: i64) -> Vector {
+        Vector::new(self.x * times, self.y * times)
+    }
+}
+
+impl Neg for Vector {
+    type Output = Vector;
+
+    fn neg(self) -> Vector {
+        Vector::new(-self.x, -self.y)
+    }
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction {
+    pub const ALL: [Self; 4] = [Self::Up, Self::Down, Self::Left, Self::Right];
+
+    pub fn vector(self) -> Vector {
+        match self {
+            Direction::Up => Vector::UP,
+            Direction::Down => Vector::DOWN,
+            Direction::Left => Vector::LEFT,
+            Direction::Right => Vector::RIGHT,
+        }
+    }
+
+    pub fn opposite(self) -> Self {
+        match self {
+            Direction::Up => Direction::Down,
+            Direction::Down => Direction::Up,
+            Direction::Left => Direction::Right,
+            Direction::Right => Direction::Left,
+        }
+    }
+}
+
+impl From<Direction> for Vector {
+    fn from(direction: Direction) -> Self {
+        direction.vector()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn adds_and_subtracts() {
+        let point = Point::new(2, 3);
+        assert_eq!(point + Vector::new(1, -4), Point::new(3, -1));
+        assert_eq!(point - Vector::RIGHT, Point::new(1, 3));
+        assert_eq!(Point::new(5, 5) - point, Vector::new(3, 2));
+        assert_eq!(Vector::UP * 3 + Vector::LEFT, Vector::new(-1, -3));
+
+        let mut point = point;
+        point += Direction::Down.into();
+        assert_eq!(point, Point::new(2, 4));
+    }
+
+    #[test]
+    fn measures_distances() {
+        let (a, b) = (Point::new(1, 1), Point::new(4, -1));
+        assert_eq!(a.manhattan(b), 5);
+        assert_eq!(a.chebyshev(b), 3);
+        assert_eq!(b.manhattan(a), 5);
+    }
+
+    #[test]
+    fn steps_towards_targets() {
+        assert_eq!(
+            Point::ORIGIN.step_towards(Point::new(5, -2)),
+            Point::new(1, -1)
+        );
+        assert_eq!(
+            Point::ORIGIN.step_towards(Point::new(0, 3)),
+            Point::new(0, 1)
+        );
+        assert_eq!(Point::ORIGIN.step_towards(Point::ORIGIN), Point::ORIGIN);
+    }
+
+    #[test]
+    fn directions_have_opposites() {
+        for direction in Direction::ALL {
+            assert_eq!(direction.opposite().vector(), -direction.vector());
+        }
+    }
+}
@@ -0,0 +1,197 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// The solution of a new day, solving nothing yet but already wired to its example.
+fn template(day: u8) -> String {
+    r#"use crate::{error::Result, solution::Solution};
+
+pub struct DayN;
+
+impl Solution for DayN {
+    type Input = Vec<String>;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(input.lines().map(String::from).collect())
+    }
+
+    fn part_one(lines: &Self::Input) -> Result<Self::PartOne> {
+        Ok(lines.len())
+    }
+
+    fn part_two(_lines: &Self::Input) -> Result<Self::PartTwo> {
+        Ok(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::solve;
+
+    const EXAMPLE: &str = include_str!("../../data/day_N.example.txt");
+
+    #[test]
+    fn solves_example() {
+        // Replace with the answers given in the puzzle
+        let solved = solve::<DayN>(EXAMPLE).unwrap();
+        assert_eq!(solved.part_one, EXAMPLE.lines().count().into());
+        assert_eq!(solved.part_two, 0.into());
+    }
+}
+"#
+    .replace("DayN", &format!("Day{}", day))
+    .replace("day_N", &format!("day_{}", day))
+}
+
+/// Adds a day to the `mod` list and the `DAYS` table of `lib.rs`, keeping both in order.
+fn register(lib: &str, day: u8) -> Result<String, String> {
+    let name = format!("day_{}", day);
+    let module = format!("pub mod {};", name);
+    let entry = format!("    day!({}, day_{}::Day{}),", day, day, day);
+
+    if lib.lines().any(|line| line == module) {
+        return Err(format!("day_{} is already declared in lib.rs", day));
+    }
+
+    let mut lines = lib.lines().map(String::from).collect::<Vec<_>>();
+
+    let mods = lines
+        .iter()
+        .position(|line| line.starts_with("pub mod "))
+        .ok_or("no `pub mod` list found in lib.rs")?;
+    let at = lines[mods..]
+        .iter()
+        .position(|line| {
+            line.strip_prefix("pub mod ")
+                .and_then(|line| line.strip_suffix(';'))
+                .map_or(true, |other| other > name.as_str())
+        })
+        .map_or(lines.len(), |offset| mods + offset);
+    lines.insert(at, module);
+
+    let table = lines
+        .iter()
+        .position(|line| line.starts_with("pub const DAYS: [Day; "))
+        .ok_or("no `DAYS` table found in lib.rs")?;
+    let size = lines[table]
+        .trim_start_matches("pub const DAYS: [Day; ")
+        .split(']')
+        .next()
+        .and_then(|size| size.parse::<usize>().ok())
+        .ok_or("invalid size of the `DAYS` table")?;
+    lines[table] = format!("pub const DAYS: [Day; {}] = [", size + 1);
+
+    let end = lines[table..]
+        .iter()
+        .position(|line| line == "];")
+        .map(|offset| table + offset)
+        .ok_or("unterminated `DAYS` table")?;
+    let at = lines[table + 1..end]
+        .iter()
+        .position(|line| {
+            line.trim_start_matches("    day!(")
+                .split(',')
+                .next()
+                .and_then(|number| number.parse::<u8>().ok())
+                .is_some_and(|number| number > day)
+        })
+        .map_or(end, |offset| table + 1 + offset);
+    lines.insert(at, entry);
+
+    Ok(lines.join("\n") + "\n")
+}
+
+fn create(path: &Path, contents: &str) -> Result<(), String> {
+    if path.exists() {
+        return Err(format!("{} already exists", path.display()));
+    }
+
+    fs::create_dir_all(path.parent().unwrap_or(Path::new(".")))
+        .and_then(|_| fs::write(path, contents))
+        .map_err(|e| format!("could not write {}: {}", path.display(), e))
+}
+
+/// Creates the module of a day, an empty input and an empty example in the crate at `root`, then
+/// registers the day in `lib.rs`. Returns every file created or changed.
+pub fn new_day(root: &Path, day: u8) -> Result<Vec<PathBuf>, String> {
+    let lib_path = root.join("src").join("lib.rs");
+    let lib = fs::read_to_string(&lib_path)
+        .map_err(|e| format!("could not read {}: {}", lib_path.display(), e))?;
+    let lib = register(&lib, day)?;
+
+    let module = root.join("src").join(format!("day_{}", day)).join("mod.rs");
+    let input = root.join("data").join(format!("day_{}.txt", day));
+    let example = root.join("data").join(format!("day_{}.example.txt", day));
+
+    for path in [&module, &input, &example] {
+        if path.exists() {
+            return Err(format!("{} already exists", path.display()));
+        }
+    }
+
+    create(&module, &template(day))?;
+    create(&input, "")?;
+    create(&example, "")?;
+    fs::write(&lib_path, lib)
+        .map_err(|e| format!("could not write {}: {}", lib_path.display(), e))?;
+
+    Ok(vec![module, input, example, lib_path])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LIB: &str = "pub mod day_1;
+pub mod day_10;
+pub mod day_2;
+pub mod error;
+
+pub const DAYS: [Day; 3] = [
+    day!(1, day_1::Day1),
+    day!(2, day_2::Day2),
+    day!(10, day_10::Day10),
+];
+";
+
+    #[test]
+    fn registers_days_in_order() {
+        assert_eq!(
+            register(LIB, 3).unwrap(),
+            "pub mod day_1;
+pub mod day_10;
+pub mod day_2;
+pub mod day_3;
+pub mod error;
+
+pub const DAYS: [Day; 4] = [
+    day!(1, day_1::Day1),
+    day!(2, day_2::Day2),
+    day!(3, day_3::Day3),
+    day!(10, day_10::Day10),
+];
+"
+        );
+
+        let lib = register(LIB, 13).unwrap();
+        assert!(lib.contains("pub mod day_10;\npub mod day_13;\npub mod day_2;"));
+        assert!(lib.contains("day!(10, day_10::Day10),\n    day!(13, day_13::Day13),\n];"));
+    }
+
+    #[test]
+    fn refuses_registered_days() {
+        assert!(register(LIB, 2).is_err());
+    }
+
+    #[test]
+    fn fills_in_the_day() {
+        let module = template(13);
+        assert!(module.contains("pub struct Day13;"));
+        assert!(module.contains("../../data/day_13.example.txt"));
+        assert!(!module.contains("DayN"));
+    }
+}
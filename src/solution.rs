@@ -0,0 +1,141 @@
+use std::{
+    fmt, thread,
+    time::{Duration, Instant},
+};
+
+use crate::error::Result;
+
+/// The answer to one part of a puzzle, as printed by the runner.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Number(i64),
+    Text(String),
+    /// Rows of pixels to be read by a human, like the screen of day 10.
+    Image(Vec<String>),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Number(n) => write!(f, "{}", n),
+            Answer::Text(s) => write!(f, "{}", s),
+            Answer::Image(rows) => write!(f, "{}", rows.join("\n")),
+        }
+    }
+}
+
+macro_rules! impl_from_number {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(value: $t) -> Self {
+                    i64::try_from(value)
+                        .map(Answer::Number)
+                        .unwrap_or_else(|_| Answer::Text(value.to_string()))
+                }
+            }
+        )*
+    };
+}
+
+impl_from_number!(i32, u32, i64, u64, usize);
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::Text(value)
+    }
+}
+
+/// Shared shape of every day: parse the input once, then solve both parts from it.
+pub trait Solution {
+    type Input;
+    type PartOne: Into<Answer>;
+    type PartTwo: Into<Answer>;
+
+    /// Whether spaces at the end of lines are part of the puzzle, rather than a copy-paste accident.
+    const KEEPS_TRAILING_WHITESPACE: bool = false;
+
+    /// Whether `parse` already works out both parts, leaving the parts only to read the result.
+    const SOLVES_WHILE_PARSING: bool = false;
+
+    fn parse(input: &str) -> Result<Self::Input>;
+
+    fn part_one(input: &Self::Input) -> Result<Self::PartOne>;
+
+    fn part_two(input: &Self::Input) -> Result<Self::PartTwo>;
+}
+
+/// Wall-clock time spent on each step of a solution.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Timings {
+    pub parse: Duration,
+    pub part_one: Duration,
+    pub part_two: Duration,
+}
+
+impl Timings {
+    pub fn total(&self) -> Duration {
+        self.parse + self.part_one + self.part_two
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Solved {
+    pub part_one: Answer,
+    pub part_two: Answer,
+    pub timings: Timings,
+}
+
+fn timed<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let result = f();
+    (result, start.elapsed())
+}
+
+/// Parses the input and runs both parts of the given solution, timing every step.
+pub fn solve<S: Solution>(input: &str) -> Result<Solved> {
+    let (input, parse) = timed(|| S::parse(input));
+    let input = input?;
+
+    let (part_one, part_one_time) = timed(|| S::part_one(&input));
+    let (part_two, part_two_time) = timed(|| S::part_two(&input));
+
+    Ok(Solved {
+        part_one: part_one?.into(),
+        part_two: part_two?.into(),
+        timings: Timings {
+            parse,
+            part_one: part_one_time,
+            part_two: part_two_time,
+        },
+    })
+}
+
+/// Like [`solve`], but runs both parts at the same time on their own threads.
+pub fn solve_parallel<S>(input: &str) -> Result<Solved>
+where
+    S: Solution,
+    S::Input: Sync,
+    S::PartOne: Send,
+    S::PartTwo: Send,
+{
+    let (input, parse) = timed(|| S::parse(input));
+    let input = input?;
+
+    let ((part_one, part_one_time), (part_two, part_two_time)) = thread::scope(|scope| {
+        let part_one = scope.spawn(|| timed(|| S::part_one(&input)));
+        let part_two = timed(|| S::part_two(&input));
+
+        (part_one.join().expect("part one panicked"), part_two)
+    });
+
+    Ok(Solved {
+        part_one: part_one?.into(),
+        part_two: part_two?.into(),
+        timings: Timings {
+            parse,
+            part_one: part_one_time,
+            part_two: part_two_time,
+        },
+    })
+}
@@ -0,0 +1,156 @@
+use std::{
+    env, fs, io,
+    path::{Path, PathBuf},
+    process::Command,
+    time::SystemTime,
+};
+
+use advent_of_code_22::solution::Answer;
+
+/// The file holding the solution of a day. The running binary only picks up changes to it after
+/// a [`rebuild`] and a [`restart`].
+pub fn source_file(day: u8) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("src")
+        .join(format!("day_{}", day))
+        .join("mod.rs")
+}
+
+/// Builds the runner again with cargo, in the profile of the running binary. Returns whether the
+/// build succeeded, its output going straight to the terminal.
+pub fn rebuild() -> bool {
+    let cargo = env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+    let mut command = Command::new(cargo);
+    command
+        .args(["build", "--bin", "aoc"])
+        .current_dir(env!("CARGO_MANIFEST_DIR"));
+
+    if !cfg!(debug_assertions) {
+        command.arg("--release");
+    }
+
+    command.status().is_ok_and(|status| status.success())
+}
+
+/// Replaces the running process with `exe`, given the same arguments. Only returns if that fails.
+/// Where processes cannot be replaced, runs `exe` and exits with its status instead.
+pub fn restart(exe: &Path) -> io::Error {
+    let mut command = Command::new(exe);
+    command.args(env::args_os().skip(1));
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        command.exec()
+    }
+
+    #[cfg(not(unix))]
+    match command.status() {
+        Ok(status) => std::process::exit(status.code().unwrap_or(1)),
+        Err(error) => error,
+    }
+}
+
+/// Modification times of a set of files, `None` for the ones that cannot be read.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Stamps(Vec<(PathBuf, Option<SystemTime>)>);
+
+impl Stamps {
+    pub fn of(paths: Vec<PathBuf>) -> Self {
+        Self(
+            paths
+                .into_iter()
+                .map(|path| {
+                    let modified = fs::metadata(&path).and_then(|m| m.modified()).ok();
+                    (path, modified)
+                })
+                .collect(),
+        )
+    }
+
+    /// Files that were added, removed or modified since the `previous` stamps.
+    pub fn changed_since<'a>(&'a self, previous: &'a Self) -> Vec<&'a Path> {
+        self.0
+            .iter()
+            .filter(|stamp| !previous.0.contains(stamp))
+            .map(|(path, _)| path.as_path())
+            .chain(
+                previous
+                    .0
+                    .iter()
+                    .filter(|(path, _)| !self.0.iter().any(|(p, _)| p == path))
+                    .map(|(path, _)| path.as_path()),
+            )
+            .collect()
+    }
+}
+
+/// How an answer compares to the one of the previous run.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Change<'a> {
+    New,
+    Unchanged,
+    Changed(&'a Answer),
+}
+
+impl<'a> Change<'a> {
+    pub fn new(previous: Option<&'a Answer>, current: &Answer) -> Self {
+        match previous {
+            None => Change::New,
+            Some(previous) if previous == current => Change::Unchanged,
+            Some(previous) => Change::Changed(previous),
+        }
+    }
+}
+
+pub fn print_change(part: u8, answer: &Answer, change: &Change) {
+    match (answer, change) {
+        (Answer::Image(_), Change::New) => println!("  Part {}:\n{}", part, answer),
+        (Answer::Image(_), Change::Unchanged) => {
+            println!("  Part {} (unchanged):\n{}", part, answer)
+        }
+        (Answer::Image(_), Change::Changed(previous)) => {
+            println!(
+                "  Part {} (changed):\n{}\n  was:\n{}",
+                part, answer, previous
+            )
+        }
+        (_, Change::New) => println!("  Part {}: {}", part, answer),
+        (_, Change::Unchanged) => println!("  Part {}: {} (unchanged)", part, answer),
+        (_, Change::Changed(previous)) => {
+            println!("  Part {}: {} (was {})", part, answer, previous)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compares_with_the_previous_answer() {
+        let answer = Answer::from(24000);
+        let other = Answer::from(25000);
+
+        assert_eq!(Change::new(None, &answer), Change::New);
+        assert_eq!(Change::new(Some(&answer), &answer), Change::Unchanged);
+        assert_eq!(Change::new(Some(&other), &answer), Change::Changed(&other));
+    }
+
+    #[test]
+    fn notices_added_removed_and_modified_files() {
+        let stamp = |path: &str, secs| {
+            let modified = SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(secs);
+            (PathBuf::from(path), Some(modified))
+        };
+
+        let previous = Stamps(vec![stamp("a", 1), stamp("b", 1), stamp("c", 1)]);
+        let current = Stamps(vec![stamp("a", 1), stamp("b", 2), stamp("d", 1)]);
+
+        assert_eq!(
+            current.changed_since(&previous),
+            vec![Path::new("b"), Path::new("d"), Path::new("c")]
+        );
+        assert!(current.changed_since(&current).is_empty());
+    }
+}
@@ -0,0 +1,71 @@
+//! Regression tests running every day against the answers recorded in `data/answers.toml`.
+
+use advent_of_code_22::{
+    answers::{Answers, Check},
+    input::{self, Source},
+    DAYS,
+};
+
+fn answers() -> Answers {
+    Answers::load(&input::data_dir().join("answers.toml")).unwrap()
+}
+
+fn verify(source: impl Fn(u8) -> Vec<Source>) {
+    let answers = answers();
+
+    for day in DAYS.iter() {
+        for source in source(day.number) {
+            let input = input::read(day.number, source).unwrap();
+            let solved = (day.solve)(&input).unwrap();
+            let expected = answers.get(day.number, source).cloned().unwrap_or_default();
+
+            for (part, expected, actual) in [
+                (1, expected.part_one, solved.part_one),
+                (2, expected.part_two, solved.part_two),
+            ] {
+                let check = Check::new(expected.as_ref(), &actual);
+                assert!(
+                    !matches!(check, Check::Fail { .. }),
+                    "day {} ({}), part {}: {:?}",
+                    day.number,
+                    source,
+                    part,
+                    check
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn real_inputs_match_the_known_answers() {
+    verify(|_| vec![Source::Real]);
+}
+
+#[test]
+fn examples_match_the_known_answers() {
+    verify(input::examples);
+}
+
+#[test]
+fn every_day_has_an_example() {
+    for day in DAYS.iter() {
+        assert!(
+            !input::examples(day.number).is_empty(),
+            "day {}",
+            day.number
+        );
+    }
+}
+
+#[test]
+fn parallel_parts_match_sequential_ones() {
+    for day in DAYS.iter() {
+        let input = input::read(day.number, Source::Example(1)).unwrap();
+        let sequential = (day.solve)(&input).unwrap();
+        let parallel = (day.solve_parallel)(&input).unwrap();
+
+        assert_eq!(sequential.part_one, parallel.part_one, "day {}", day.number);
+        assert_eq!(sequential.part_two, parallel.part_two, "day {}", day.number);
+    }
+}
@@ -0,0 +1,26 @@
+//! Checks that every day module in `src` is registered in `DAYS`, so none is left out of the runner.
+
+use std::{fs, path::Path};
+
+use advent_of_code_22::DAYS;
+
+#[test]
+fn registers_every_day_module() {
+    let src = Path::new(env!("CARGO_MANIFEST_DIR")).join("src");
+    let mut modules = fs::read_dir(src)
+        .unwrap()
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().into_string().ok()?;
+            let day = name.strip_prefix("day_")?.parse::<u8>().ok()?;
+            entry.path().join("mod.rs").is_file().then_some(day)
+        })
+        .collect::<Vec<_>>();
+    modules.sort_unstable();
+
+    let registered = DAYS.iter().map(|day| day.number).collect::<Vec<_>>();
+    assert_eq!(
+        registered, modules,
+        "the `DAYS` table of src/lib.rs should list every src/day_N module, in order"
+    );
+}